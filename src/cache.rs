@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An on-disk cache of answers that rarely change, such as geocoder results
+/// and stop register data.
+///
+/// Each table is a JSON file in the cache directory mapping keys to values.
+/// Keys are compared without case or surrounding whitespace, so `Tyholt` and
+/// ` tyholt` share an entry. The cache is best effort: unreadable files count
+/// as misses and failed writes are ignored.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+    refresh: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+#[allow(non_snake_case)]
+struct Entry {
+    /// Unix timestamp in seconds.
+    storedAt: i64,
+    value: Value,
+}
+
+impl Cache {
+    /// How long entries are used before they are fetched again.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: Self::DEFAULT_TTL,
+            refresh: false,
+        }
+    }
+
+    /// `~/.cache/busterminal` on Linux, and the platform's equivalent
+    /// elsewhere.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("busterminal"))
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Ignore stored entries and fetch everything again, replacing what is
+    /// stored.
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The value stored under `key`, unless it is older than the TTL or the
+    /// cache is being refreshed.
+    pub fn get<T: DeserializeOwned>(&self, table: &str, key: &str) -> Option<T> {
+        if self.refresh {
+            return None;
+        }
+
+        let entry = self.read(table).remove(&normalize(key))?;
+
+        if self.expired(&entry) {
+            return None;
+        }
+
+        serde_json::from_value(entry.value).ok()
+    }
+
+    /// The value stored under `key` however old it is, for when fetching a
+    /// fresh one failed. Expired entries last until the next `put` to the
+    /// same table.
+    pub fn get_stale<T: DeserializeOwned>(&self, table: &str, key: &str) -> Option<T> {
+        let entry = self.read(table).remove(&normalize(key))?;
+
+        serde_json::from_value(entry.value).ok()
+    }
+
+    /// Stores `value` under `key` and drops the entries that have expired, so
+    /// tables do not grow without limit.
+    pub fn put<T: Serialize>(&self, table: &str, key: &str, value: &T) {
+        let Ok(value) = serde_json::to_value(value) else {
+            return;
+        };
+
+        let mut entries = self.read(table);
+        entries.retain(|_, entry| !self.expired(entry));
+        entries.insert(
+            normalize(key),
+            Entry {
+                storedAt: Utc::now().timestamp(),
+                value,
+            },
+        );
+
+        let Ok(text) = serde_json::to_string(&entries) else {
+            return;
+        };
+
+        if fs::create_dir_all(&self.dir).is_ok() {
+            let _ = fs::write(self.path(table), text);
+        }
+    }
+
+    fn expired(&self, entry: &Entry) -> bool {
+        let age = Utc::now().timestamp().saturating_sub(entry.storedAt);
+
+        age < 0 || age as u64 >= self.ttl.as_secs()
+    }
+
+    fn read(&self, table: &str) -> HashMap<String, Entry> {
+        fs::read_to_string(self.path(table))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn path(&self, table: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", table))
+    }
+}
+
+fn normalize(key: &str) -> String {
+    key.trim().to_lowercase()
+}
@@ -1,5 +1,8 @@
+mod builder;
 mod entur_client;
-mod wrapper;
+mod graphql;
+mod queries;
 
+pub use builder::{DEFAULT_BASE_URL, DEFAULT_CLIENT_NAME, EnTurClientBuilder};
 pub use entur_client::EnTurClient;
-pub use wrapper::Wrapper;
+pub use graphql::GraphQLError;
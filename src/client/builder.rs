@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use http::header::{HeaderMap, HeaderValue};
+
+use super::EnTurClient;
+use crate::cache::Cache;
+use crate::error::BusterminalError;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.entur.io";
+pub const DEFAULT_CLIENT_NAME: &str = "tmnio-sanntidsappen-dev";
+
+/// Configures an [`EnTurClient`].
+///
+/// Entur asks every consumer to identify itself through the `ET-Client-Name`
+/// header, so tools embedding busterminal should set their own client name.
+#[derive(Debug, Clone)]
+pub struct EnTurClientBuilder {
+    base_url: String,
+    client_name: String,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+    cache: Option<Cache>,
+}
+
+impl Default for EnTurClientBuilder {
+    fn default() -> Self {
+        Self {
+            base_url: String::from(DEFAULT_BASE_URL),
+            client_name: String::from(DEFAULT_CLIENT_NAME),
+            timeout: None,
+            user_agent: None,
+            proxy: None,
+            cache: None,
+        }
+    }
+}
+
+impl EnTurClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn client_name(mut self, client_name: impl Into<String>) -> Self {
+        self.client_name = client_name.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Keep geocoder results and stop register data in `cache` between runs.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn build(self) -> Result<EnTurClient, BusterminalError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let client_name = HeaderValue::from_str(&self.client_name).map_err(|_| {
+            BusterminalError::InvalidConfig(format!("Invalid client name: {}", self.client_name))
+        })?;
+        headers.insert("ET-Client-Name", client_name);
+
+        let mut http_client = reqwest::Client::builder().default_headers(headers);
+
+        if let Some(timeout) = self.timeout {
+            http_client = http_client.timeout(timeout);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            http_client = http_client.user_agent(user_agent);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy).map_err(|error| {
+                BusterminalError::InvalidConfig(format!("Invalid proxy {}: {}", proxy, error))
+            })?;
+            http_client = http_client.proxy(proxy);
+        }
+
+        let base_url = self.base_url.trim_end_matches('/').to_string();
+
+        Ok(EnTurClient::with_http_client(
+            http_client.build()?,
+            base_url,
+            self.cache,
+        ))
+    }
+}
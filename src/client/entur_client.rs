@@ -1,283 +1,699 @@
-use http::header::{HeaderMap, HeaderValue};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use super::builder::EnTurClientBuilder;
+use super::graphql::{GraphQLRequest, GraphQLResponse};
+use super::queries;
+use crate::cache::Cache;
+use crate::departure::DepartureOptions;
+use crate::error::BusterminalError;
+use crate::filter::DepartureFilter;
+use crate::model::{
+    AuthoritiesResponse, Geocode, Journey, LineInfo, LineResponse, LinesResponse, Location, Mode,
+    NearbyStopPlace, NearestResponse, Organisation, QuaysResponse, ServiceJourneyResponse,
+    StopInfo, StopInfoResponse, StopPlace, StopPlaceInfo, StopPlaceResponse, TripResponse, Vehicle,
+    VehiclesResponse, dedup_situations,
+};
+use crate::trip::TripOptions;
+
+/// Extra cost, in seconds of travel time, of each transfer when the user
+/// prefers fewer transfers.
+const FEWER_TRANSFERS_PENALTY: u32 = 600;
+
+/// How many pages of trip patterns `plan_trip` looks through for ones that
+/// stay within the longest walk.
+const MAX_WALK_PAGES: usize = 5;
+
+/// How many stop places `get_nearby_stop_places` asks for.
+const NEARBY_STOP_PLACES: u32 = 10;
 
 pub struct EnTurClient {
     http_client: reqwest::Client,
     base_url: String,
+    cache: Option<Cache>,
+}
+
+impl Default for EnTurClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EnTurClient {
+    /// Creates a client against the public Entur API with the default client name.
     pub fn new() -> Self {
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            http::header::CONTENT_TYPE,
-            HeaderValue::from_str("application/json").unwrap(),
-        );
-
-        headers.insert(
-            "ET-Client-Name",
-            HeaderValue::from_str("tmnio-sanntidsappen-dev").unwrap(),
-        );
-
-        let http_client = reqwest::Client::builder()
-            .default_headers(headers)
+        Self::builder()
             .build()
-            .unwrap();
+            .expect("default client configuration is valid")
+    }
 
-        let base_url = String::from("https://api.entur.io");
+    pub fn builder() -> EnTurClientBuilder {
+        EnTurClientBuilder::new()
+    }
 
+    pub(super) fn with_http_client(
+        http_client: reqwest::Client,
+        base_url: String,
+        cache: Option<Cache>,
+    ) -> Self {
         Self {
             http_client,
             base_url,
+            cache,
         }
     }
 
+    /// The on-disk cache set with [`EnTurClientBuilder::cache`], if any.
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<String, BusterminalError> {
+        let response: reqwest::Response = request.send().await?;
+        let status = response.status();
+
+        if !status.is_success() {
+            return Err(BusterminalError::HttpStatus {
+                status,
+                url: response.url().to_string(),
+            });
+        }
+
+        Ok(response.text().await?)
+    }
+
+    async fn graphql<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        request: &GraphQLRequest,
+    ) -> Result<T, BusterminalError> {
+        let url: String = format!("{}{}", self.base_url, path);
+        let body: String = serde_json::to_string(request)?;
+
+        let text: String = self.send(self.http_client.post(&url).body(body)).await?;
+        let response: GraphQLResponse<T> = serde_json::from_str(&text)?;
+
+        if !response.errors.is_empty() {
+            return Err(BusterminalError::GraphQL(response.errors));
+        }
+
+        response.data.ok_or_else(|| {
+            BusterminalError::Decode(serde::de::Error::custom(
+                "response did not contain any data",
+            ))
+        })
+    }
+
+    /// Answers from the cache when it has a fresh entry, and otherwise runs
+    /// `fetch` and stores what it returns. A stale entry stands in when the
+    /// API cannot be reached or answers with a server error.
+    async fn cached<T: Serialize + DeserializeOwned>(
+        &self,
+        table: &str,
+        key: &str,
+        fetch: impl Future<Output = Result<T, BusterminalError>>,
+    ) -> Result<T, BusterminalError> {
+        let Some(cache) = &self.cache else {
+            return fetch.await;
+        };
+
+        if let Some(value) = cache.get(table, key) {
+            return Ok(value);
+        }
+
+        match fetch.await {
+            Ok(value) => {
+                cache.put(table, key, &value);
+                Ok(value)
+            }
+            Err(error) if error.is_unavailable() => cache.get_stale(table, key).ok_or(error),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Journey Planner API
     pub async fn get_stop_place(
         &self,
         stop_id: &str,
+        options: &DepartureOptions,
+        filter: &DepartureFilter,
+    ) -> Result<StopPlace, BusterminalError> {
+        let mut request = GraphQLRequest::new(queries::STOP_PLACE).variable("id", stop_id);
+
+        if let Some(start_time) = options.start_time {
+            request = request.variable("startTime", start_time.to_rfc3339());
+        }
+
+        if let Some(window) = options.window {
+            request = request.variable("timeRange", window.as_secs());
+        }
+
+        if let Some(limit) = options.limit {
+            request = request.variable("numberOfDepartures", limit);
+        }
+
+        if options.arrivals {
+            request = request.variable("arrivalDeparture", "arrivals");
+        }
+
+        let line_ids = self.white_listed_lines(filter).await?;
+        if !line_ids.is_empty() {
+            request = request.variable("whiteListed", json!({ "lines": line_ids }));
+        }
+
+        if !filter.modes.is_empty() {
+            let modes: Vec<&str> = filter.modes.iter().map(Mode::as_str).collect();
+            request = request.variable("whiteListedModes", modes);
+        }
+
+        let response: StopPlaceResponse = self
+            .graphql("/journey-planner/v3/graphql", &request)
+            .await?;
+
+        let mut stop_place = response
+            .stopPlace
+            .ok_or_else(|| BusterminalError::NoMatch(format!("Unknown stop place: {}", stop_id)))?;
+
+        stop_place.estimatedCalls.retain(|call| {
+            let allowed = if options.arrivals {
+                call.forAlighting
+            } else {
+                call.forBoarding
+            };
+
+            allowed && filter.matches(call)
+        });
+
+        for call in &mut stop_place.estimatedCalls {
+            dedup_situations(&mut call.situations);
+        }
+
+        Ok(stop_place)
+    }
+
+    /// The line ids in `filter` together with the ids of every line whose
+    /// public code is in it.
+    async fn white_listed_lines(
+        &self,
+        filter: &DepartureFilter,
+    ) -> Result<Vec<String>, BusterminalError> {
+        let mut ids: Vec<String> = filter.line_ids().map(String::from).collect();
+
+        for code in filter.line_codes() {
+            let lines = self.lines(Some(code), None).await?;
+
+            ids.extend(
+                lines
+                    .into_iter()
+                    .filter(|line| line.public_code().eq_ignore_ascii_case(code))
+                    .map(|line| line.id),
+            );
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+
+        Ok(ids)
+    }
+
+    /// Journey Planner API: the stop places within `radius` meters of a
+    /// coordinate, closest first, each with its next few departures.
+    pub async fn get_nearby_stop_places(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius: f64,
         start_time: &str,
-    ) -> Result<String, reqwest::Error> {
-        let url: String = format!("{}/journey-planner/v3/graphql", self.base_url);
-        let query: String = format!(
-            r#"
-{{
-  "query": "
-  {{
-    stopPlace(id: \"{}\")
-    {{
-      id
-      name
-      estimatedCalls(
-        startTime: \"{}\",
-        timeRange: 72100,
-        numberOfDepartures: 50
-      ) {{
-        realtime
-        aimedDepartureTime
-        expectedDepartureTime
-        date
-        forBoarding
-        destinationDisplay {{
-          frontText
-        }}
-        quay {{
-          id
-          name
-          publicCode
-          description
-        }}
-        serviceJourney {{
-          id
-          journeyPattern {{
-            line {{
-              id
-              publicCode
-              name
-              transportMode
-            }}
-          }}
-        }}
-      }}
-    }}
-  }}"
-}}"#,
-            stop_id, start_time
-        )
-        .replace('\n', "");
-
-        let res: Result<reqwest::Response, reqwest::Error> =
-            self.http_client.post(&url).body(query).send().await;
-
-        let data: reqwest::Response = match res {
-            Ok(response) => response,
-            Err(error) => panic!("Request error: {}", error),
-        };
+    ) -> Result<Vec<NearbyStopPlace>, BusterminalError> {
+        let request = GraphQLRequest::new(queries::NEAREST)
+            .variable("latitude", latitude)
+            .variable("longitude", longitude)
+            .variable("maximumDistance", radius)
+            .variable("maximumResults", NEARBY_STOP_PLACES)
+            .variable("startTime", start_time);
+
+        let response: NearestResponse = self
+            .graphql("/journey-planner/v3/graphql", &request)
+            .await?;
+
+        let mut stop_places: Vec<NearbyStopPlace> = response
+            .nearest
+            .edges
+            .into_iter()
+            .map(|edge| edge.node)
+            .collect();
 
-        data.text().await
+        for nearby in &mut stop_places {
+            nearby.place.estimatedCalls.retain(|call| call.forBoarding);
+
+            for call in &mut nearby.place.estimatedCalls {
+                dedup_situations(&mut call.situations);
+            }
+        }
+
+        stop_places.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        Ok(stop_places)
+    }
+
+    /// Journey Planner API: every call of a service journey on `date`
+    /// (`YYYY-MM-DD`).
+    pub async fn journey(&self, journey_id: &str, date: &str) -> Result<Journey, BusterminalError> {
+        let request = GraphQLRequest::new(queries::SERVICE_JOURNEY)
+            .variable("id", journey_id)
+            .variable("date", date);
+
+        let response: ServiceJourneyResponse = self
+            .graphql("/journey-planner/v3/graphql", &request)
+            .await?;
+
+        response.serviceJourney.ok_or_else(|| {
+            BusterminalError::NoMatch(format!("Unknown service journey: {}", journey_id))
+        })
+    }
+
+    /// Journey Planner API: a line with its authority, operator and journey
+    /// patterns.
+    pub async fn get_line(&self, line_id: &str) -> Result<LineInfo, BusterminalError> {
+        let request = GraphQLRequest::new(queries::LINE).variable("id", line_id);
+
+        let response: LineResponse = self
+            .graphql("/journey-planner/v3/graphql", &request)
+            .await?;
+
+        response
+            .line
+            .ok_or_else(|| BusterminalError::NoMatch(format!("Unknown line: {}", line_id)))
     }
 
-    #[allow(dead_code)]
-    pub async fn journey(
+    /// Journey Planner API: lines whose public code or name matches `query`,
+    /// best match first. `region` narrows the search to a codespace such as
+    /// `ATB` or an authority name such as `Ruter`.
+    ///
+    /// Lines with exactly that public code are asked for first; only when
+    /// there are none are all lines in the region fetched and matched here.
+    pub async fn find_lines(
         &self,
-        journey_id: &str,
-        date_time: &str,
-    ) -> Result<String, reqwest::Error> {
-        let url: String = format!("{}//journey-planner/v3/graphql", self.base_url);
-        let query: String = format!(
-            r#"
-{{
-"query": "
-{{
-serviceJourney(id: \"{}\") {{
-        estimatedCalls(date: \"{}\") {{
-            aimedDepartureTime
-            expectedDepartureTime
-            quay {{
-                id
-                name
-            }}
-        }}
-    }}
-}}"
-}}
-"#,
-            journey_id, date_time
-        )
-        .replace('\n', "");
-
-        let res = self.http_client.post(&url).body(query).send().await;
-
-        let data: reqwest::Response = match res {
-            Ok(response) => response,
-            Err(error) => panic!("Request error: {}", error),
+        query: &str,
+        region: Option<&str>,
+    ) -> Result<Vec<LineInfo>, BusterminalError> {
+        let query = query.trim();
+        let authorities = match region {
+            Some(region) => Some(self.authorities_in(region).await?),
+            None => None,
         };
 
-        data.text().await
+        let mut candidates = self.lines(Some(query), authorities.as_deref()).await?;
+        if candidates.is_empty() {
+            candidates = self.lines(None, authorities.as_deref()).await?;
+        }
+
+        let mut lines: Vec<(u8, LineInfo)> = candidates
+            .into_iter()
+            .filter_map(|line| Some((line_match(&line, query)?, line)))
+            .collect();
+
+        if lines.is_empty() {
+            return Err(BusterminalError::NoMatch(match region {
+                Some(region) => format!(
+                    "Could not find any lines in {} using query: {}",
+                    region, query
+                ),
+                None => format!("Could not find any lines using query: {}", query),
+            }));
+        }
+
+        lines.sort_by(|(a_score, a), (b_score, b)| {
+            (a_score, a.public_code().len(), a.public_code()).cmp(&(
+                b_score,
+                b.public_code().len(),
+                b.public_code(),
+            ))
+        });
+
+        Ok(lines.into_iter().map(|(_, line)| line).collect())
+    }
+
+    /// Lines with `public_code`, or every line, of the given authorities or
+    /// of all of Norway. Cached, as the list rarely changes.
+    async fn lines(
+        &self,
+        public_code: Option<&str>,
+        authorities: Option<&[String]>,
+    ) -> Result<Vec<LineInfo>, BusterminalError> {
+        let mut request = GraphQLRequest::new(queries::LINES);
+        if let Some(public_code) = public_code {
+            request = request.variable("publicCode", public_code);
+        }
+        if let Some(authorities) = authorities {
+            request = request.variable("authorities", authorities);
+        }
+
+        let key = format!(
+            "{}:{}",
+            public_code.unwrap_or("*"),
+            authorities.map(|ids| ids.join(",")).unwrap_or_default()
+        );
+        let response: LinesResponse = self
+            .cached(
+                "lines",
+                &key,
+                self.graphql("/journey-planner/v3/graphql", &request),
+            )
+            .await?;
+
+        Ok(response.lines)
+    }
+
+    /// Ids of the authorities in a codespace (`ATB`) or with a name (`AtB`).
+    async fn authorities_in(&self, region: &str) -> Result<Vec<String>, BusterminalError> {
+        let request = GraphQLRequest::new(queries::AUTHORITIES);
+
+        let response: AuthoritiesResponse = self
+            .cached(
+                "lines",
+                "authorities",
+                self.graphql("/journey-planner/v3/graphql", &request),
+            )
+            .await?;
+
+        let ids: Vec<String> = response
+            .authorities
+            .into_iter()
+            .filter(|authority| in_region(authority, region))
+            .map(|authority| authority.id)
+            .collect();
+
+        if ids.is_empty() {
+            return Err(BusterminalError::NoMatch(format!(
+                "Unknown region: {}. Use a codespace such as ATB or an authority such as Ruter",
+                region
+            )));
+        }
+
+        Ok(ids)
+    }
+
+    /// Vehicle positions API: the vehicles currently running on a line, each
+    /// with the quay it is at or heading for looked up in the journey planner.
+    pub async fn vehicles(&self, line_id: &str) -> Result<Vec<Vehicle>, BusterminalError> {
+        let request = GraphQLRequest::new(queries::VEHICLES).variable("lineRef", line_id);
+
+        let response: VehiclesResponse = self
+            .graphql("/realtime/v2/vehicles/graphql", &request)
+            .await?;
+        let mut vehicles = response.vehicles;
+
+        if vehicles.is_empty() {
+            return Err(BusterminalError::NoMatch(format!(
+                "No vehicles are reporting their position on line: {}",
+                line_id
+            )));
+        }
+
+        let mut quay_ids: Vec<&str> = vehicles
+            .iter()
+            .filter_map(|vehicle| vehicle.monitoredCall.as_ref()?.stopPointRef.as_deref())
+            .collect();
+        quay_ids.sort_unstable();
+        quay_ids.dedup();
+
+        // The quay names are best effort: without them the next stop falls
+        // back to its id, which beats hiding the positions already fetched.
+        if !quay_ids.is_empty() {
+            let request = GraphQLRequest::new(queries::QUAYS).variable("ids", quay_ids);
+
+            let response: QuaysResponse = self
+                .graphql("/journey-planner/v3/graphql", &request)
+                .await
+                .unwrap_or_default();
+
+            for vehicle in &mut vehicles {
+                let quay_id = vehicle
+                    .monitoredCall
+                    .as_ref()
+                    .and_then(|call| call.stopPointRef.as_deref());
+
+                vehicle.nextQuay = response
+                    .quays
+                    .iter()
+                    .find(|quay| Some(quay.id.as_str()) == quay_id)
+                    .cloned();
+            }
+        }
+
+        vehicles.sort_by(|a, b| {
+            (&a.destinationName, &a.vehicleId).cmp(&(&b.destinationName, &b.vehicleId))
+        });
+
+        Ok(vehicles)
+    }
+
+    /// Plans a trip between two stop place ids or [`Location`]s.
+    pub async fn plan_trip(
+        &self,
+        from: impl Into<Location>,
+        to: impl Into<Location>,
+        options: &TripOptions,
+    ) -> Result<TripResponse, BusterminalError> {
+        let mut request = GraphQLRequest::new(queries::TRIP)
+            .variable("from", location_input(&from.into()))
+            .variable("to", location_input(&to.into()));
+
+        if let Some(date_time) = options.date_time {
+            request = request
+                .variable("dateTime", date_time.to_rfc3339())
+                .variable("arriveBy", options.arrive_by);
+        }
+
+        if let Some(modes) = options.transport_modes() {
+            if modes.is_empty() {
+                return Err(BusterminalError::InvalidConfig(String::from(
+                    "No modes of transport left to travel with",
+                )));
+            }
+
+            let transport_modes: Vec<Value> = modes
+                .iter()
+                .map(|mode| json!({ "transportMode": mode.as_str() }))
+                .collect();
+            request = request.variable("modes", json!({ "transportModes": transport_modes }));
+        }
+
+        if let Some(max_transfers) = options.max_transfers {
+            request = request.variable("maximumTransfers", max_transfers);
+        }
+
+        if let Some(walk_speed) = options.walk_speed {
+            request = request.variable("walkSpeed", walk_speed);
+        }
+
+        if options.wheelchair {
+            request = request.variable("wheelchairAccessible", true);
+        }
+
+        if options.prefer_fewer_transfers {
+            request = request.variable("transferPenalty", FEWER_TRANSFERS_PENALTY);
+        }
+
+        if let Some(count) = options.count {
+            request = request.variable("numTripPatterns", count);
+        }
+
+        if let Some(cursor) = &options.page_cursor {
+            request = request.variable("pageCursor", cursor.as_str());
+        }
+
+        let mut response: TripResponse = self
+            .graphql("/journey-planner/v3/graphql", &request)
+            .await?;
+
+        // Entur cannot limit the walk, so patterns that walk too far are
+        // dropped here and later pages fetched until enough are left.
+        if let Some(max_walk_distance) = options.max_walk_distance {
+            let wanted = options.count.unwrap_or(1) as usize;
+            let trip = &mut response.trip;
+            trip.tripPatterns
+                .retain(|pattern| pattern.walkDistance <= max_walk_distance);
+
+            let mut pages = 1;
+            while trip.tripPatterns.len() < wanted
+                && pages < MAX_WALK_PAGES
+                && let Some(cursor) = trip.nextPageCursor.take()
+            {
+                let page: TripResponse = self
+                    .graphql(
+                        "/journey-planner/v3/graphql",
+                        &request.clone().variable("pageCursor", cursor),
+                    )
+                    .await?;
+
+                trip.tripPatterns.extend(
+                    page.trip
+                        .tripPatterns
+                        .into_iter()
+                        .filter(|pattern| pattern.walkDistance <= max_walk_distance),
+                );
+                trip.nextPageCursor = page.trip.nextPageCursor;
+                pages += 1;
+            }
+        }
+
+        for leg in response
+            .trip
+            .tripPatterns
+            .iter_mut()
+            .flat_map(|pattern| pattern.legs.iter_mut())
+        {
+            dedup_situations(&mut leg.situations);
+        }
+
+        Ok(response)
     }
 
     /// StopRegister API
+    pub async fn get_stop_info(&self, stop_id: &str) -> Result<StopPlaceInfo, BusterminalError> {
+        self.cached("stops", stop_id, self.fetch_stop_info(stop_id))
+            .await
+    }
+
+    async fn fetch_stop_info(&self, stop_id: &str) -> Result<StopPlaceInfo, BusterminalError> {
+        let request = GraphQLRequest::new(queries::STOP_INFO).variable("id", stop_id);
+
+        let response: StopInfoResponse = self.graphql("/stop-places/v1/graphql", &request).await?;
+
+        response
+            .stopPlace
+            .into_iter()
+            .next()
+            .ok_or_else(|| BusterminalError::NoMatch(format!("Unknown stop place: {}", stop_id)))
+    }
+
+    /// StopRegister API: a stop place together with its parent and adjacent
+    /// stop places.
+    pub async fn get_stop_details(&self, stop_id: &str) -> Result<StopInfo, BusterminalError> {
+        let stop_place = self.get_stop_info(stop_id).await?;
 
-    #[allow(dead_code)]
-    pub async fn get_stop_info(&self, stop_id: &str) -> Result<String, reqwest::Error> {
-        let url = format!("{}/stop-places/v1/graphql", self.base_url);
-
-        let query: String = format!(
-            r#"
-{{
-"query": "{{
-    stopPlace(id: \"{}\", stopPlaceType: onstreetBus) {{
-        id
-        name {{
-            value
-        }}
-        ... on StopPlace {{
-            quays {{
-                id
-                compassBearing
-                geometry {{
-                    type
-                    coordinates
-                }}
-            }}
-        }}
-    }}
-}}"
-}}
-"#,
-            stop_id
-        )
-        .replace('\n', "");
-
-        let res: Result<reqwest::Response, reqwest::Error> =
-            self.http_client.post(&url).body(query).send().await;
-
-        let data: reqwest::Response = match res {
-            Ok(response) => response,
-            Err(error) => panic!("Request error: {}", error),
+        let parent = match &stop_place.parentSiteRef {
+            Some(site) => self.get_related_stop_info(&site.r#ref).await?,
+            None => None,
         };
 
-        data.text().await
+        let mut adjacent = Vec::with_capacity(stop_place.adjacentSites.len());
+        for site in &stop_place.adjacentSites {
+            adjacent.extend(self.get_related_stop_info(&site.r#ref).await?);
+        }
+
+        Ok(StopInfo {
+            stopPlace: stop_place,
+            parent,
+            adjacent,
+        })
+    }
+
+    /// A parent or adjacent site, or `None` when the stop register no longer
+    /// has it.
+    async fn get_related_stop_info(
+        &self,
+        stop_id: &str,
+    ) -> Result<Option<StopPlaceInfo>, BusterminalError> {
+        match self.get_stop_info(stop_id).await {
+            Ok(info) => Ok(Some(info)),
+            Err(BusterminalError::NoMatch(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
     }
 
     /// Geocoder API
-    pub async fn get_autocomplete_stop_name(&self, query: &str) -> Result<String, reqwest::Error> {
-        let url = format!(
-            "{}/geocoder/v1/autocomplete?text={}&layers=venue",
-            self.base_url, query
-        );
+    pub async fn get_autocomplete_stop_name(
+        &self,
+        query: &str,
+    ) -> Result<Geocode, BusterminalError> {
+        let geocode = self.autocomplete(query, "venue").await?;
 
-        let res = self.http_client.get(&url).send().await;
+        if geocode.features.is_empty() {
+            return Err(BusterminalError::NoMatch(format!(
+                "Could not find any stops using query: {}",
+                query
+            )));
+        }
 
-        let data = match res {
-            Ok(response) => response,
-            Err(error) => panic!("Request error: {}", error),
-        };
+        Ok(geocode)
+    }
+
+    /// Geocoder API, searching street addresses as well as stops.
+    pub async fn get_autocomplete_place(&self, query: &str) -> Result<Geocode, BusterminalError> {
+        let geocode = self.autocomplete(query, "venue,address").await?;
+
+        if geocode.features.is_empty() {
+            return Err(BusterminalError::NoMatch(format!(
+                "Could not find any stops or addresses using query: {}",
+                query
+            )));
+        }
 
-        data.text().await
+        Ok(geocode)
     }
 
-    #[allow(dead_code)]
-    pub async fn plan_trip(&self, from: &String, to: &String) -> Result<String, reqwest::Error> {
-        let url: String = format!("{}/journey-planner/v3/graphql", self.base_url);
-
-        let query: String = format!(
-            r#"
-{{
-"query": "
-{{
-  trip(
-    from: {{
-      place: \"{from}\"
-    }},
-    to: {{
-      place: \"{to}\"
-    }}
-  ) {{
-    tripPatterns {{
-      duration
-      walkDistance
-      legs {{
-        expectedStartTime
-        expectedEndTime
-        duration
-        mode
-        distance
-        line {{
-          id
-          publicCode
-          name
-          transportMode
-        }}
-        fromEstimatedCall {{
-          quay {{
-            id
-            name
-            publicCode
-          }}
-          date
-          forBoarding
-          realtime
-          aimedDepartureTime
-          expectedDepartureTime
-          actualDepartureTime
-          destinationDisplay {{
-            frontText
-          }}
-        }}
-        toEstimatedCall {{
-          quay {{
-            id
-            name
-            publicCode
-          }}
-          date
-          forBoarding
-          realtime
-          aimedDepartureTime
-          expectedDepartureTime
-          actualDepartureTime
-          destinationDisplay {{
-            frontText
-          }}
-        }}
-      }}
-    }}
-  }}
-}}"
-}}
-"#,
-            from = from,
-            to = to
-        )
-        .replace('\n', "");
-
-        let res: Result<reqwest::Response, reqwest::Error> =
-            self.http_client.post(&url).body(query).send().await;
-
-        let data: reqwest::Response = match res {
-            Ok(response) => response,
-            Err(error) => panic!("Request error: {}", error),
-        };
+    async fn autocomplete(&self, query: &str, layers: &str) -> Result<Geocode, BusterminalError> {
+        let key = format!("{}:{}", layers, query);
+
+        self.cached("geocoder", &key, self.fetch_autocomplete(query, layers))
+            .await
+    }
+
+    async fn fetch_autocomplete(
+        &self,
+        query: &str,
+        layers: &str,
+    ) -> Result<Geocode, BusterminalError> {
+        let url = format!("{}/geocoder/v1/autocomplete", self.base_url);
+
+        let request = self
+            .http_client
+            .get(&url)
+            .query(&[("text", query), ("layers", layers)]);
+
+        Ok(serde_json::from_str(&self.send(request).await?)?)
+    }
+}
+
+/// How well a line matches a search, lower is better: the exact public code,
+/// a public code starting with the query, or the query anywhere in the code
+/// or name.
+fn line_match(line: &LineInfo, query: &str) -> Option<u8> {
+    let query = query.trim().to_lowercase();
+    let code = line.public_code().to_lowercase();
+
+    if code == query {
+        Some(0)
+    } else if code.starts_with(&query) {
+        Some(1)
+    } else if code.contains(&query) || line.name().to_lowercase().contains(&query) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn in_region(authority: &Organisation, region: &str) -> bool {
+    let codespace = authority.id.split(':').next().unwrap_or_default();
+
+    codespace.eq_ignore_ascii_case(region) || authority.name.eq_ignore_ascii_case(region)
+}
 
-        data.text().await
+/// The journey planner's `Location` input.
+fn location_input(location: &Location) -> Value {
+    match location {
+        Location::Place(id) => json!({ "place": id }),
+        Location::Coordinates {
+            latitude,
+            longitude,
+            name,
+        } => json!({
+            "name": name,
+            "coordinates": { "latitude": latitude, "longitude": longitude },
+        }),
     }
 }
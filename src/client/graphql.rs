@@ -0,0 +1,61 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A GraphQL request body: `{"query": ..., "variables": {...}}`.
+///
+/// Values are always passed as variables so user input never ends up
+/// inside the query document itself.
+#[derive(Serialize, Debug, Clone)]
+pub struct GraphQLRequest {
+    query: &'static str,
+    variables: Map<String, Value>,
+}
+
+impl GraphQLRequest {
+    pub fn new(query: &'static str) -> Self {
+        Self {
+            query,
+            variables: Map::new(),
+        }
+    }
+
+    pub fn variable(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.variables.insert(name.to_string(), value.into());
+        self
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GraphQLResponse<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<GraphQLError>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GraphQLError {
+    pub message: String,
+    #[serde(default)]
+    pub path: Vec<Value>,
+}
+
+impl fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            return write!(f, "{}", self.message);
+        }
+
+        let path: Vec<String> = self
+            .path
+            .iter()
+            .map(|segment| match segment {
+                Value::String(name) => name.clone(),
+                other => other.to_string(),
+            })
+            .collect();
+
+        write!(f, "{} (at {})", self.message, path.join("."))
+    }
+}
@@ -0,0 +1,428 @@
+// Selections shared by several queries. `concat!` only takes literals, so
+// they are macros rather than constants.
+macro_rules! departure_call_fragment {
+    () => {
+        r#"
+fragment departureCall on EstimatedCall {
+  realtime
+  aimedDepartureTime
+  expectedDepartureTime
+  aimedArrivalTime
+  expectedArrivalTime
+  date
+  forBoarding
+  forAlighting
+  cancellation
+  predictionInaccurate
+  occupancyStatus
+  destinationDisplay {
+    frontText
+  }
+  situations {
+    ...situation
+  }
+  quay {
+    id
+    name
+    publicCode
+    description
+  }
+  serviceJourney {
+    id
+    origin: quays(first: 1) {
+      id
+      name
+      publicCode
+      description
+    }
+    journeyPattern {
+      line {
+        id
+        publicCode
+        name
+        transportMode
+      }
+    }
+  }
+}
+"#
+    };
+}
+
+macro_rules! situation_fragment {
+    () => {
+        r#"
+fragment situation on PtSituationElement {
+  id
+  situationNumber
+  severity
+  summary {
+    language
+    value
+  }
+  description {
+    language
+    value
+  }
+  advice {
+    language
+    value
+  }
+  validityPeriod {
+    startTime
+    endTime
+  }
+}
+"#
+    };
+}
+
+/// Journey Planner API
+pub const STOP_PLACE: &str = concat!(
+    r#"
+query StopPlace(
+  $id: String!,
+  $startTime: DateTime,
+  $timeRange: Int = 72100,
+  $numberOfDepartures: Int = 50,
+  $arrivalDeparture: ArrivalDeparture = departures,
+  $whiteListed: InputWhiteListed,
+  $whiteListedModes: [TransportMode]
+) {
+  stopPlace(id: $id) {
+    id
+    name
+    estimatedCalls(
+      startTime: $startTime,
+      timeRange: $timeRange,
+      numberOfDepartures: $numberOfDepartures,
+      arrivalDeparture: $arrivalDeparture,
+      whiteListed: $whiteListed,
+      whiteListedModes: $whiteListedModes
+    ) {
+      ...departureCall
+    }
+  }
+}
+"#,
+    departure_call_fragment!(),
+    situation_fragment!()
+);
+
+pub const NEAREST: &str = concat!(
+    r#"
+query Nearest(
+  $latitude: Float!,
+  $longitude: Float!,
+  $maximumDistance: Float!,
+  $maximumResults: Int,
+  $startTime: DateTime
+) {
+  nearest(
+    latitude: $latitude,
+    longitude: $longitude,
+    maximumDistance: $maximumDistance,
+    maximumResults: $maximumResults,
+    filterByPlaceTypes: [stopPlace],
+    multiModalMode: parent
+  ) {
+    edges {
+      node {
+        distance
+        place {
+          id
+          ... on StopPlace {
+            name
+            estimatedCalls(startTime: $startTime, timeRange: 72100, numberOfDepartures: 5) {
+              ...departureCall
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#,
+    departure_call_fragment!(),
+    situation_fragment!()
+);
+
+pub const SERVICE_JOURNEY: &str = r#"
+query ServiceJourney($id: String!, $date: Date) {
+  serviceJourney(id: $id) {
+    id
+    line {
+      id
+      publicCode
+      name
+      transportMode
+    }
+    estimatedCalls(date: $date) {
+      realtime
+      cancellation
+      aimedArrivalTime
+      expectedArrivalTime
+      actualArrivalTime
+      aimedDepartureTime
+      expectedDepartureTime
+      actualDepartureTime
+      destinationDisplay {
+        frontText
+      }
+      quay {
+        id
+        name
+        publicCode
+        description
+      }
+    }
+  }
+}
+"#;
+
+pub const TRIP: &str = concat!(
+    r#"
+query Trip(
+  $from: Location!,
+  $to: Location!,
+  $dateTime: DateTime,
+  $arriveBy: Boolean,
+  $modes: Modes,
+  $maximumTransfers: Int,
+  $walkSpeed: Float,
+  $wheelchairAccessible: Boolean,
+  $transferPenalty: Int,
+  $numTripPatterns: Int,
+  $pageCursor: String
+) {
+  trip(
+    from: $from,
+    to: $to,
+    dateTime: $dateTime,
+    arriveBy: $arriveBy,
+    modes: $modes,
+    maximumTransfers: $maximumTransfers,
+    walkSpeed: $walkSpeed,
+    wheelchairAccessible: $wheelchairAccessible,
+    transferPenalty: $transferPenalty,
+    numTripPatterns: $numTripPatterns,
+    pageCursor: $pageCursor
+  ) {
+    nextPageCursor
+    previousPageCursor
+    tripPatterns {
+      duration
+      walkDistance
+      legs {
+        expectedStartTime
+        expectedEndTime
+        duration
+        mode
+        distance
+        line {
+          id
+          publicCode
+          name
+          transportMode
+        }
+        fromPlace {
+          name
+          latitude
+          longitude
+        }
+        fromEstimatedCall {
+          ...legCall
+        }
+        toEstimatedCall {
+          ...legCall
+        }
+        situations {
+          ...situation
+        }
+      }
+    }
+  }
+}
+
+fragment legCall on EstimatedCall {
+  quay {
+    id
+    name
+    publicCode
+  }
+  date
+  forBoarding
+  realtime
+  cancellation
+  predictionInaccurate
+  occupancyStatus
+  aimedDepartureTime
+  expectedDepartureTime
+  actualDepartureTime
+  destinationDisplay {
+    frontText
+  }
+}
+"#,
+    situation_fragment!()
+);
+
+pub const LINE: &str = r#"
+query Line($id: ID!) {
+  line(id: $id) {
+    id
+    publicCode
+    name
+    transportMode
+    authority {
+      id
+      name
+    }
+    operator {
+      id
+      name
+    }
+    journeyPatterns {
+      id
+      name
+      directionType
+      quays {
+        id
+        name
+        publicCode
+        description
+      }
+    }
+  }
+}
+"#;
+
+pub const LINES: &str = r#"
+query Lines($publicCode: String, $authorities: [String]) {
+  lines(publicCode: $publicCode, authorities: $authorities) {
+    id
+    publicCode
+    name
+    transportMode
+    authority {
+      id
+      name
+    }
+  }
+}
+"#;
+
+pub const AUTHORITIES: &str = r#"
+query Authorities {
+  authorities {
+    id
+    name
+  }
+}
+"#;
+
+pub const QUAYS: &str = r#"
+query Quays($ids: [String]) {
+  quays(ids: $ids) {
+    id
+    name
+    publicCode
+    description
+  }
+}
+"#;
+
+/// StopRegister API
+pub const STOP_INFO: &str = r#"
+query StopInfo($id: String) {
+  stopPlace(id: $id) {
+    id
+    name {
+      value
+    }
+    ... on StopPlace {
+      stopPlaceType
+      transportMode
+      geometry {
+        type
+        coordinates
+      }
+      accessibilityAssessment {
+        ...accessibility
+      }
+      parentSiteRef {
+        ref
+      }
+      adjacentSites {
+        ref
+      }
+      quays {
+        id
+        publicCode
+        compassBearing
+        description {
+          value
+        }
+        geometry {
+          type
+          coordinates
+        }
+        accessibilityAssessment {
+          ...accessibility
+        }
+      }
+    }
+    ... on ParentStopPlace {
+      children {
+        id
+        name {
+          value
+        }
+        stopPlaceType
+        transportMode
+      }
+    }
+  }
+}
+
+fragment accessibility on AccessibilityAssessment {
+  limitations {
+    wheelchairAccess
+    stepFreeAccess
+    escalatorFreeAccess
+    liftFreeAccess
+    audibleSignalsAvailable
+    visualSignsAvailable
+  }
+}
+"#;
+
+/// Vehicle positions API
+pub const VEHICLES: &str = r#"
+query Vehicles($lineRef: String) {
+  vehicles(lineRef: $lineRef) {
+    vehicleId
+    line {
+      lineRef
+      publicCode
+      lineName
+    }
+    serviceJourney {
+      id
+    }
+    destinationName
+    mode
+    lastUpdated
+    delay
+    location {
+      latitude
+      longitude
+    }
+    bearing
+    monitoredCall {
+      stopPointRef
+      vehicleAtStop
+    }
+  }
+}
+"#;
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::BusterminalError;
+use crate::model::{Feature, Location};
+
+/// The user's settings, stored as TOML in [`Config::default_path`].
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Config {
+    /// Saved stops and addresses by alias, used as `@alias` on the command
+    /// line.
+    #[serde(default)]
+    pub favourites: BTreeMap<String, Favourite>,
+}
+
+/// A stop or address picked from the geocoder once and saved under an alias.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Favourite {
+    /// Stop place id, or the geocoder id of an address.
+    pub id: String,
+    pub name: String,
+    /// Only set for street addresses, which trips go to by coordinates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f32>,
+}
+
+impl Favourite {
+    pub fn from_feature(feature: &Feature) -> Self {
+        let [longitude, latitude] = feature.geometry.coordinates;
+        let is_address = feature.properties.is_address();
+
+        Self {
+            id: feature.properties.id.clone(),
+            name: feature.properties.name.clone(),
+            latitude: is_address.then_some(latitude),
+            longitude: is_address.then_some(longitude),
+        }
+    }
+
+    pub fn is_stop(&self) -> bool {
+        self.latitude.is_none() || self.longitude.is_none()
+    }
+
+    pub fn location(&self) -> Location {
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => Location::Coordinates {
+                latitude: latitude.into(),
+                longitude: longitude.into(),
+                name: Some(self.name.clone()),
+            },
+            _ => Location::Place(self.id.clone()),
+        }
+    }
+}
+
+/// The alias in `@alias`, or `None` for anything else.
+pub fn favourite_alias(query: &str) -> Option<&str> {
+    query.strip_prefix('@')
+}
+
+impl Config {
+    /// `~/.config/busterminal/config.toml` on Linux, and the platform's
+    /// equivalent elsewhere.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("busterminal").join("config.toml"))
+    }
+
+    /// Reads the config at `path`. A missing file is an empty config.
+    pub fn load(path: &Path) -> Result<Config, BusterminalError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(error) => return Err(error.into()),
+        };
+
+        toml::from_str(&text).map_err(|error| {
+            BusterminalError::InvalidConfig(format!(
+                "Invalid config file {}: {}",
+                path.display(),
+                error.message()
+            ))
+        })
+    }
+
+    /// Writes the config to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), BusterminalError> {
+        let text = toml::to_string_pretty(self).map_err(|error| {
+            BusterminalError::InvalidConfig(format!("Could not write config: {}", error))
+        })?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, text)?;
+
+        Ok(())
+    }
+
+    /// Looks up a favourite by alias, with or without the leading `@`.
+    pub fn favourite(&self, alias: &str) -> Result<&Favourite, BusterminalError> {
+        let alias = favourite_alias(alias).unwrap_or(alias);
+
+        self.favourites.get(alias).ok_or_else(|| {
+            BusterminalError::NoMatch(format!(
+                "Unknown favourite: @{}. Save it with `busterminal fav add {}`",
+                alias, alias
+            ))
+        })
+    }
+}
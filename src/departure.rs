@@ -0,0 +1,165 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::model::{EstimatedCall, Mode};
+
+/// Which departures to fetch from a stop place. The default is Entur's next
+/// 50 departures within 20 hours from now.
+///
+/// Calls where passengers cannot board, or cannot alight when asking for
+/// arrivals, are left out.
+#[derive(Debug, Default, Clone)]
+pub struct DepartureOptions {
+    /// Earliest departure. Now when unset.
+    pub start_time: Option<DateTime<FixedOffset>>,
+    /// How far past `start_time` to look.
+    pub window: Option<Duration>,
+    /// Most departures to fetch.
+    pub limit: Option<u32>,
+    /// Fetch arrivals instead of departures.
+    pub arrivals: bool,
+}
+
+/// How `departure --group-by` splits a board into sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// One section per quay or platform, in platform order.
+    Quay,
+    /// One section per line, in line order.
+    Line,
+    /// One section per destination, soonest first.
+    Direction,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "quay" => Ok(GroupBy::Quay),
+            "line" => Ok(GroupBy::Line),
+            "direction" => Ok(GroupBy::Direction),
+            other => Err(format!(
+                "unknown grouping '{}', expected quay, line or direction",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GroupBy::Quay => write!(f, "quay"),
+            GroupBy::Line => write!(f, "line"),
+            GroupBy::Direction => write!(f, "direction"),
+        }
+    }
+}
+
+/// One section of a grouped departure board.
+#[derive(Debug)]
+pub struct DepartureGroup<'a> {
+    /// e.g. `Spor 4`, `12 Dragvoll - Lade` or `Towards Lade via sentrum`.
+    pub title: String,
+    pub departures: Vec<&'a EstimatedCall>,
+}
+
+/// Splits `departures` into groups, keeping the first `per_group` departures
+/// of each.
+pub fn group_departures(
+    departures: &[EstimatedCall],
+    group_by: GroupBy,
+    per_group: usize,
+) -> Vec<DepartureGroup<'_>> {
+    let mut keys: Vec<String> = Vec::new();
+    let mut groups: Vec<((u64, String), DepartureGroup)> = Vec::new();
+
+    for call in departures {
+        let key = group_key(call, group_by);
+
+        let index = match keys.iter().position(|existing| *existing == key) {
+            Some(index) => index,
+            None => {
+                keys.push(key);
+                groups.push((
+                    sort_key(call, group_by),
+                    DepartureGroup {
+                        title: group_title(call, group_by),
+                        departures: Vec::new(),
+                    },
+                ));
+                groups.len() - 1
+            }
+        };
+
+        let group = &mut groups[index].1;
+        if group.departures.len() < per_group {
+            group.departures.push(call);
+        }
+    }
+
+    if group_by != GroupBy::Direction {
+        groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// Quays and lines are sorted by their public code, directions are kept in
+/// order of their first departure.
+fn sort_key(call: &EstimatedCall, group_by: GroupBy) -> (u64, String) {
+    match group_by {
+        GroupBy::Quay => code_order(call.quay.publicCode.as_deref()),
+        GroupBy::Line => code_order(call.line().map(|line| line.publicCode.as_str())),
+        GroupBy::Direction => (0, String::new()),
+    }
+}
+
+fn group_key(call: &EstimatedCall, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::Quay => call.quay.id.clone(),
+        GroupBy::Line => call.line().map(|line| line.id.clone()).unwrap_or_default(),
+        GroupBy::Direction => call.destinationDisplay.frontText.clone(),
+    }
+}
+
+fn group_title(call: &EstimatedCall, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::Quay => {
+            let quay = &call.quay;
+            let rail = call
+                .line()
+                .is_some_and(|line| line.transportMode == Mode::rail.as_str());
+
+            let mut title = match (&quay.publicCode, rail) {
+                (Some(code), true) => format!("Spor {}", code),
+                (Some(code), false) => format!("Platform {}", code),
+                (None, _) => quay.name.clone(),
+            };
+
+            if let Some(description) = &quay.description {
+                title.push_str(&format!(" · {}", description));
+            }
+
+            title
+        }
+        GroupBy::Line => match call.line() {
+            Some(line) => format!("{} {}", line.publicCode, line.name),
+            None => String::from("Other"),
+        },
+        GroupBy::Direction => format!("Towards {}", call.destinationDisplay.frontText),
+    }
+}
+
+/// Sorts public codes the way they are signposted: `2` before `10`, and
+/// numbers before letters.
+fn code_order(code: Option<&str>) -> (u64, String) {
+    let code = code.unwrap_or_default();
+    let digits: String = code.chars().take_while(char::is_ascii_digit).collect();
+
+    (digits.parse().unwrap_or(u64::MAX), code.to_string())
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+use std::io;
+
+use reqwest::StatusCode;
+
+use crate::client::GraphQLError;
+
+/// Errors surfaced by busterminal. Each variant maps to its own process exit
+/// code so scripts can tell "nothing found" apart from "the API is down".
+#[derive(Debug)]
+pub enum BusterminalError {
+    /// The request never completed (DNS, connection refused, timeout, ...).
+    Transport(reqwest::Error),
+    /// The API answered with a non-success HTTP status.
+    HttpStatus { status: StatusCode, url: String },
+    /// The GraphQL endpoint returned an `errors` array.
+    GraphQL(Vec<GraphQLError>),
+    /// The response body did not match the expected shape.
+    Decode(serde_json::Error),
+    /// The request succeeded but nothing matched the query.
+    NoMatch(String),
+    /// The client or command line was configured with invalid values.
+    InvalidConfig(String),
+    /// Writing output failed.
+    Io(io::Error),
+}
+
+impl BusterminalError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BusterminalError::NoMatch(_) => 3,
+            BusterminalError::Transport(_) => 4,
+            BusterminalError::HttpStatus { .. } => 5,
+            BusterminalError::GraphQL(_) => 6,
+            BusterminalError::Decode(_) => 7,
+            BusterminalError::InvalidConfig(_) => 2,
+            BusterminalError::Io(_) => 1,
+        }
+    }
+
+    /// The API could not be reached or failed on its side (5xx), as opposed
+    /// to answering that the request was wrong.
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            BusterminalError::Transport(_) => true,
+            BusterminalError::HttpStatus { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for BusterminalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BusterminalError::Transport(error) => write!(f, "Request error: {}", error),
+            BusterminalError::HttpStatus { status, url } => {
+                write!(f, "Unexpected response {} from {}", status, url)
+            }
+            BusterminalError::GraphQL(errors) => {
+                let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "GraphQL error: {}", messages.join("; "))
+            }
+            BusterminalError::Decode(error) => write!(f, "Could not parse response: {}", error),
+            BusterminalError::NoMatch(message) => write!(f, "{}", message),
+            BusterminalError::InvalidConfig(message) => write!(f, "{}", message),
+            BusterminalError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for BusterminalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BusterminalError::Transport(error) => Some(error),
+            BusterminalError::Decode(error) => Some(error),
+            BusterminalError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for BusterminalError {
+    fn from(error: reqwest::Error) -> Self {
+        BusterminalError::Transport(error)
+    }
+}
+
+impl From<serde_json::Error> for BusterminalError {
+    fn from(error: serde_json::Error) -> Self {
+        BusterminalError::Decode(error)
+    }
+}
+
+impl From<io::Error> for BusterminalError {
+    fn from(error: io::Error) -> Self {
+        BusterminalError::Io(error)
+    }
+}
@@ -0,0 +1,118 @@
+use crate::model::{EstimatedCall, Mode};
+
+/// Narrows down the departures from a stop place.
+///
+/// Lines and modes are sent to Entur as part of the query, with public line
+/// codes looked up as line ids first, so that `numberOfDepartures` is not used
+/// up by other lines. Quays and destinations are matched locally.
+#[derive(Debug, Default, Clone)]
+pub struct DepartureFilter {
+    /// Public line codes (`"3"`) or line ids (`"RUT:Line:3"`).
+    pub lines: Vec<String>,
+    pub modes: Vec<Mode>,
+    /// Quay public codes (`"4"`), platform names as in the `--group-by quay`
+    /// headings (`"Spor 4"`, `"Platform B"`) or quay ids.
+    pub quays: Vec<String>,
+    /// Text the destination display must contain.
+    pub towards: Option<String>,
+}
+
+impl DepartureFilter {
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+            && self.modes.is_empty()
+            && self.quays.is_empty()
+            && self.towards.is_none()
+    }
+
+    /// Line ids given as they are, ready to be white-listed in the query.
+    pub fn line_ids(&self) -> impl Iterator<Item = &str> {
+        self.lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| is_id(line))
+    }
+
+    /// Public line codes, which have to be looked up as line ids before they
+    /// can be white-listed.
+    pub fn line_codes(&self) -> impl Iterator<Item = &str> {
+        self.lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !is_id(line))
+    }
+
+    pub fn matches(&self, call: &EstimatedCall) -> bool {
+        self.matches_line(call)
+            && self.matches_mode(call)
+            && self.matches_quay(call)
+            && self.matches_towards(call)
+    }
+
+    fn matches_line(&self, call: &EstimatedCall) -> bool {
+        if self.lines.is_empty() {
+            return true;
+        }
+
+        let Some(line) = call.line() else {
+            return false;
+        };
+
+        self.lines
+            .iter()
+            .any(|wanted| wanted == &line.id || wanted.eq_ignore_ascii_case(&line.publicCode))
+    }
+
+    fn matches_mode(&self, call: &EstimatedCall) -> bool {
+        if self.modes.is_empty() {
+            return true;
+        }
+
+        let Some(line) = call.line() else {
+            return false;
+        };
+
+        self.modes
+            .iter()
+            .any(|mode| mode.as_str().eq_ignore_ascii_case(&line.transportMode))
+    }
+
+    fn matches_quay(&self, call: &EstimatedCall) -> bool {
+        if self.quays.is_empty() {
+            return true;
+        }
+
+        let quay = &call.quay;
+
+        self.quays.iter().any(|wanted| {
+            let wanted = wanted.trim();
+
+            wanted == quay.id
+                || quay.publicCode.as_deref().is_some_and(|code| {
+                    wanted.eq_ignore_ascii_case(code)
+                        || wanted.eq_ignore_ascii_case(&format!("Spor {}", code))
+                        || wanted.eq_ignore_ascii_case(&format!("Platform {}", code))
+                })
+                || quay
+                    .description
+                    .as_deref()
+                    .is_some_and(|description| wanted.eq_ignore_ascii_case(description))
+        })
+    }
+
+    fn matches_towards(&self, call: &EstimatedCall) -> bool {
+        let Some(towards) = &self.towards else {
+            return true;
+        };
+
+        call.destinationDisplay
+            .frontText
+            .to_lowercase()
+            .contains(&towards.to_lowercase())
+    }
+}
+
+/// NeTEx ids look like `CODESPACE:Type:value`.
+fn is_id(value: &str) -> bool {
+    value.split(':').count() == 3
+}
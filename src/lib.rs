@@ -0,0 +1,32 @@
+//! Client and terminal rendering for Norwegian public transport data from
+//! [Entur](https://developer.entur.org).
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), busterminal::BusterminalError> {
+//! use busterminal::EnTurClient;
+//!
+//! let client = EnTurClient::new();
+//! let geocode = client.get_autocomplete_stop_name("Tyholt").await?;
+//! let stop_id = &geocode.features[0].properties.id;
+//! let stop_place = client.get_stop_place(stop_id, &Default::default(), &Default::default()).await?;
+//!
+//! busterminal::render::print_departures(&mut std::io::stdout(), &stop_place.estimatedCalls)?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod cache;
+pub mod client;
+pub mod config;
+pub mod departure;
+pub mod error;
+pub mod filter;
+pub mod model;
+pub mod prompt;
+pub mod render;
+pub mod time;
+pub mod trip;
+pub mod watch;
+
+pub use client::EnTurClient;
+pub use error::BusterminalError;
@@ -1,25 +1,48 @@
-#![allow(non_snake_case)]
-
-use std::io::{self, Write};
-mod client;
-mod model;
-
-use model::{EstimatedCall, Feature, Geocode, Mode, StopPlaceResponse, TripPattern, TripResponse};
-
-use client::{EnTurClient, Wrapper};
-
-use chrono::{DateTime, Utc};
-use clap::{Args, Parser};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use busterminal::cache::Cache;
+use busterminal::client::{DEFAULT_BASE_URL, DEFAULT_CLIENT_NAME};
+use busterminal::config::{self, Config, Favourite};
+use busterminal::departure::{self, DepartureOptions, GroupBy};
+use busterminal::filter::DepartureFilter;
+use busterminal::model::{EstimatedCall, Feature, LineInfo, Location, Mode, Trip};
+use busterminal::prompt::{Paging, Selection};
+use busterminal::render::{self, Format};
+use busterminal::time::{self, parse_duration};
+use busterminal::trip::TripOptions;
+use busterminal::{BusterminalError, EnTurClient, prompt, watch};
+
+use chrono::{Local, NaiveTime, TimeZone, Utc};
+use clap::{ArgAction, Args, Parser};
 
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
-    let client = EnTurClient::new();
-
-    match &cli.action {
-        Action::Departure(args) => departure(&client, args).await,
-        Action::Trip(args) => trip(&client, args).await,
+    let config = cli.config.clone().or_else(Config::default_path);
+    let config = config.as_deref();
+
+    let result = match build_client(&cli) {
+        Ok(client) => match &cli.action {
+            Action::Departure(args) => departure(&client, config, args).await,
+            Action::Trip(args) => trip(&client, config, args).await,
+            Action::Nearby(args) => nearby(&client, args).await,
+            Action::Journey(args) => journey(&client, args).await,
+            Action::Stop(StopArgs {
+                action: StopAction::Info(args),
+            }) => stop_info(&client, config, args).await,
+            Action::Fav(FavArgs { action }) => fav(&client, config, action).await,
+            Action::Line(args) => line(&client, config, args).await,
+            Action::Vehicles(args) => vehicles(&client, args).await,
+        },
+        Err(error) => Err(error),
     };
+
+    if let Err(error) = result {
+        eprintln!("\x1b[31mX\x1b[0m {}", error);
+        std::process::exit(error.exit_code());
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -27,324 +50,1389 @@ async fn main() {
 struct Cli {
     #[command(subcommand)]
     action: Action,
+
+    /// Base URL of the Entur API
+    #[arg(long, global = true, env = "BUSTERMINAL_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// Value sent in the ET-Client-Name header to identify this consumer
+    #[arg(long, global = true, env = "BUSTERMINAL_CLIENT_NAME", default_value = DEFAULT_CLIENT_NAME)]
+    client_name: String,
+
+    /// Request timeout in seconds or as a duration (e.g. 10, 30s or 2m)
+    #[arg(long, global = true, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// User-Agent header sent with every request
+    #[arg(long, global = true)]
+    user_agent: Option<String>,
+
+    /// Proxy URL used for all requests
+    #[arg(long, global = true)]
+    proxy: Option<String>,
+
+    /// Search for stops and lines and fetch stop data again instead of using the cache
+    #[arg(long, global = true)]
+    refresh: bool,
+
+    /// How long cached searches, stop data and line lists are used (e.g. 12h, 7d)
+    #[arg(long, global = true, default_value = "7d", value_parser = parse_duration)]
+    cache_ttl: Duration,
+
+    /// Directory for cached searches and stop data [default: ~/.cache/busterminal]
+    #[arg(long, global = true, env = "BUSTERMINAL_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Config file with favourites [default: ~/.config/busterminal/config.toml]
+    #[arg(long, global = true, env = "BUSTERMINAL_CONFIG")]
+    config: Option<PathBuf>,
+}
+
+fn build_client(cli: &Cli) -> Result<EnTurClient, BusterminalError> {
+    let mut builder = EnTurClient::builder()
+        .base_url(&cli.base_url)
+        .client_name(&cli.client_name);
+
+    if let Some(timeout) = cli.timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    if let Some(user_agent) = &cli.user_agent {
+        builder = builder.user_agent(user_agent);
+    }
+
+    if let Some(proxy) = &cli.proxy {
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(dir) = cli.cache_dir.clone().or_else(Cache::default_dir) {
+        builder = builder.cache(Cache::new(dir).ttl(cli.cache_ttl).refresh(cli.refresh));
+    }
+
+    builder.build()
 }
 
 #[derive(Args, Debug)]
 struct DepartureArgs {
-    #[arg(short, long)]
-    stop: String,
+    #[arg(
+        short,
+        long,
+        required_unless_present = "stop_id",
+        conflicts_with = "stop_id"
+    )]
+    stop: Option<String>,
+
+    /// Use this stop place id (e.g. NSR:StopPlace:42660) instead of searching
+    #[arg(long)]
+    stop_id: Option<String>,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
+
+    /// Only show these lines, by public code or line id (e.g. 3,31)
+    #[arg(long, value_delimiter = ',')]
+    line: Vec<String>,
+
+    /// Only show these transport modes (e.g. bus,tram)
+    #[arg(long, value_delimiter = ',', value_parser = parse_transit_mode)]
+    mode: Vec<Mode>,
+
+    /// Only show departures from these quays (e.g. "Spor 4", "Platform B" or 4)
+    #[arg(long, value_delimiter = ',')]
+    quay: Vec<String>,
+
+    /// Only show departures whose destination contains this text
+    #[arg(long)]
+    towards: Option<String>,
+
+    /// Show departures from this time on (e.g. 23:30, "tomorrow 06:00" or "2026-12-24 18:00")
+    #[arg(long, conflicts_with = "watch")]
+    at: Option<String>,
+
+    /// How far ahead to look for departures (e.g. 2h, 90m) [default: 20h]
+    #[arg(long, value_parser = parse_window)]
+    window: Option<Duration>,
+
+    /// Most departures to fetch [default: 50]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    limit: Option<u32>,
+
+    /// Follow the n-th departure listed and show every stop on its journey
+    #[arg(long, conflicts_with = "watch")]
+    follow: Option<usize>,
+
+    /// Show departures in sections per quay, line or direction
+    #[arg(long, conflicts_with_all = ["arrivals", "watch", "follow"])]
+    group_by: Option<GroupBy>,
+
+    /// Departures to show in each section with --group-by
+    #[arg(
+        long,
+        default_value_t = 3,
+        requires = "group_by",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    per_group: u32,
+
+    /// Show arrivals and where they come from instead of departures
+    #[arg(long, conflicts_with = "watch")]
+    arrivals: bool,
+
+    /// Keep a live departure board open, refreshing periodically
+    #[arg(long)]
+    watch: bool,
+
+    /// How often --watch fetches new departures (e.g. 30s, 2m)
+    #[arg(long, default_value = "30s", value_parser = parse_duration, requires = "watch")]
+    interval: Duration,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+impl DepartureArgs {
+    fn options(&self) -> Result<DepartureOptions, BusterminalError> {
+        let start_time = match &self.at {
+            Some(at) => Some(
+                time::parse_date_time(at, &Local::now())
+                    .map_err(BusterminalError::InvalidConfig)?,
+            ),
+            None => None,
+        };
+
+        Ok(DepartureOptions {
+            start_time,
+            window: self.window,
+            limit: self.limit,
+            arrivals: self.arrivals,
+        })
+    }
+
+    fn filter(&self) -> DepartureFilter {
+        DepartureFilter {
+            lines: self.line.clone(),
+            modes: self.mode.clone(),
+            quays: self.quay.clone(),
+            towards: self.towards.clone(),
+        }
+    }
+}
+
+/// Departures are only filtered by public transport modes; Entur rejects
+/// `foot`, `bicycle`, `car` and `scooter` there.
+fn parse_transit_mode(value: &str) -> Result<Mode, String> {
+    let mode: Mode = value.parse()?;
+
+    if !mode.is_transit() {
+        let transit: Vec<&str> = Mode::ALL
+            .iter()
+            .filter(|mode| mode.is_transit())
+            .map(Mode::as_str)
+            .collect();
+
+        return Err(format!(
+            "'{}' is not a public transport mode, expected one of {}",
+            value,
+            transit.join(", ")
+        ));
+    }
+
+    Ok(mode)
+}
+
+/// The window is sent as a GraphQL `Int`, so it has to fit in 32 bits.
+fn parse_window(value: &str) -> Result<Duration, String> {
+    let window = parse_duration(value)?;
+
+    if window.as_secs() > i32::MAX as u64 {
+        return Err(format!(
+            "invalid window '{}', expected at most {} seconds",
+            value.trim(),
+            i32::MAX
+        ));
+    }
+
+    Ok(window)
 }
 
 #[derive(Args, Debug)]
 struct TripArgs {
-    #[arg(short, long)]
-    from: String,
+    #[arg(
+        short,
+        long,
+        required_unless_present = "from_id",
+        conflicts_with = "from_id"
+    )]
+    from: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        required_unless_present = "to_id",
+        conflicts_with = "to_id"
+    )]
+    to: Option<String>,
+
+    /// Travel from this stop place id instead of searching
+    #[arg(long)]
+    from_id: Option<String>,
+
+    /// Travel to this stop place id instead of searching
+    #[arg(long)]
+    to_id: Option<String>,
+
+    /// Pick the n-th match when --from matches several, without prompting
+    #[arg(long, requires = "from", conflicts_with = "from_first")]
+    from_pick: Option<usize>,
+
+    /// Pick the best match when --from matches several
+    #[arg(long, requires = "from")]
+    from_first: bool,
+
+    /// Pick the n-th match when --to matches several, without prompting
+    #[arg(long, requires = "to", conflicts_with = "to_first")]
+    to_pick: Option<usize>,
+
+    /// Pick the best match when --to matches several
+    #[arg(long, requires = "to")]
+    to_first: bool,
+
+    /// Leave at this time (e.g. 08:15, "tomorrow 07:30" or 2026-12-24T18:00)
+    #[arg(long, conflicts_with = "arrive_by")]
+    depart_at: Option<String>,
+
+    /// Arrive by this time (e.g. 08:15, "tomorrow 07:30" or 2026-12-24T18:00)
+    #[arg(long)]
+    arrive_by: Option<String>,
+
+    /// Travel on this day (today, tomorrow or 2026-12-24)
+    #[arg(long)]
+    date: Option<String>,
+
+    /// Only travel with these transport modes (e.g. rail,bus)
+    #[arg(long, value_delimiter = ',')]
+    modes: Vec<Mode>,
+
+    /// Never travel with these transport modes (e.g. water,air)
+    #[arg(long, value_delimiter = ',')]
+    exclude_modes: Vec<Mode>,
+
+    /// Maximum number of transfers
+    #[arg(long)]
+    max_transfers: Option<u32>,
+
+    /// Walking speed in km/h
+    #[arg(long, allow_negative_numbers = true, value_parser = parse_positive)]
+    walk_speed: Option<f64>,
+
+    /// Longest total walk in meters
+    #[arg(long, allow_negative_numbers = true, value_parser = parse_positive)]
+    max_walk: Option<f64>,
+
+    /// Only suggest wheelchair accessible trips
+    #[arg(long)]
+    wheelchair: bool,
+
+    /// Prefer trips with fewer transfers, even if they take longer
+    #[arg(long)]
+    prefer_fewer_transfers: bool,
+
+    /// Number of trips to show
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    count: Option<u32>,
+
+    /// Show later connections; repeat to skip further ahead (e.g. -nn)
+    #[arg(short, long, action = ArgAction::Count, conflicts_with = "previous")]
+    next: u8,
+
+    /// Show earlier connections; repeat to go further back (e.g. -pp)
+    #[arg(short, long, action = ArgAction::Count)]
+    previous: u8,
+
+    /// Page through later and earlier connections with n and p
+    #[arg(long)]
+    browse: bool,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+impl TripArgs {
+    fn options(&self) -> Result<TripOptions, BusterminalError> {
+        let mut now = Local::now();
+
+        if let Some(date) = &self.date {
+            let date = time::parse_date(date, now.date_naive())
+                .map_err(BusterminalError::InvalidConfig)?;
+
+            now = Local
+                .from_local_datetime(&date.and_time(now.time()))
+                .earliest()
+                .unwrap_or(now);
+        }
+
+        let date_time = match (&self.depart_at, &self.arrive_by, &self.date) {
+            (Some(time), _, _) | (None, Some(time), _) => {
+                Some(time::parse_date_time(time, &now).map_err(BusterminalError::InvalidConfig)?)
+            }
+            (None, None, Some(_)) => Some(now.fixed_offset()),
+            (None, None, None) => None,
+        };
 
-    #[arg(short, long)]
-    to: String,
+        Ok(TripOptions {
+            date_time,
+            arrive_by: self.arrive_by.is_some(),
+            modes: self.modes.clone(),
+            exclude_modes: self.exclude_modes.clone(),
+            max_transfers: self.max_transfers,
+            walk_speed: self.walk_speed.map(|speed| speed / 3.6),
+            max_walk_distance: self.max_walk,
+            wheelchair: self.wheelchair,
+            prefer_fewer_transfers: self.prefer_fewer_transfers,
+            count: self.count,
+            page_cursor: None,
+        })
+    }
+}
+
+#[derive(Args, Debug)]
+struct SelectionArgs {
+    /// Pick the n-th stop when the search matches several, without prompting
+    #[arg(long, conflicts_with = "first")]
+    pick: Option<usize>,
+
+    /// Pick the best match when the search matches several stops
+    #[arg(long)]
+    first: bool,
+}
+
+impl SelectionArgs {
+    fn selection(&self) -> Selection {
+        selection(self.pick, self.first)
+    }
+}
+
+/// Like [`SelectionArgs`], for choosing between lines with the same code.
+#[derive(Args, Debug)]
+struct LineSelectionArgs {
+    /// Pick the n-th line when the code matches several, without prompting
+    #[arg(long, conflicts_with = "first")]
+    pick: Option<usize>,
+
+    /// Pick the best match when the code matches several lines
+    #[arg(long)]
+    first: bool,
+}
+
+impl LineSelectionArgs {
+    fn selection(&self) -> Selection {
+        selection(self.pick, self.first)
+    }
+}
+
+/// How `--pick N` and `--first`, under any prefix, settle on a match.
+fn selection(pick: Option<usize>, first: bool) -> Selection {
+    match (pick, first) {
+        (Some(choice), _) => Selection::Pick(choice),
+        (None, true) => Selection::First,
+        (None, false) => Selection::Prompt,
+    }
+}
+
+#[derive(Args, Debug)]
+struct NearbyArgs {
+    /// Latitude, e.g. 59.9111
+    #[arg(long, allow_negative_numbers = true)]
+    lat: f64,
+
+    /// Longitude, e.g. 10.7528
+    #[arg(long, allow_negative_numbers = true)]
+    lon: f64,
+
+    /// Search radius in meters
+    #[arg(
+        long,
+        default_value_t = 500.0,
+        allow_negative_numbers = true,
+        value_parser = parse_positive
+    )]
+    radius: f64,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// Radii, walking speeds and walking distances must be positive numbers.
+fn parse_positive(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(number) if number.is_finite() && number > 0.0 => Ok(number),
+        _ => Err("expected a positive number".to_string()),
+    }
+}
+
+#[derive(Args, Debug)]
+struct JourneyArgs {
+    /// Service journey id, e.g. ATB:ServiceJourney:12_230306097863631_113
+    service_journey_id: String,
+
+    /// Day of the journey (today, tomorrow or 2026-12-24)
+    #[arg(long, default_value = "today")]
+    date: String,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct StopArgs {
+    #[command(subcommand)]
+    action: StopAction,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum StopAction {
+    /// Quays, accessibility and surrounding stop places of a stop
+    Info(StopInfoArgs),
+}
+
+#[derive(Args, Debug)]
+struct StopInfoArgs {
+    /// Stop name to search for, or a stop place id (e.g. NSR:StopPlace:42660)
+    stop: String,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct LineArgs {
+    /// Public line code to search for (e.g. 3 or FB73), or a line id (e.g. ATB:Line:2_3)
+    line: String,
+
+    /// Only match lines from this codespace or authority (e.g. ATB or Ruter)
+    #[arg(long)]
+    region: Option<String>,
+
+    /// Show the day's departures on the line from this stop
+    #[arg(short, long, conflicts_with = "stop_id")]
+    stop: Option<String>,
+
+    /// Use this stop place id (e.g. NSR:StopPlace:42660) instead of searching
+    #[arg(long)]
+    stop_id: Option<String>,
+
+    /// Pick the n-th stop when --stop matches several, without prompting
+    #[arg(long, requires = "stop", conflicts_with = "stop_first")]
+    stop_pick: Option<usize>,
+
+    /// Pick the best match when --stop matches several stops
+    #[arg(long, requires = "stop")]
+    stop_first: bool,
+
+    /// Day of the timetable (today, tomorrow or 2026-12-24)
+    #[arg(long, default_value = "today")]
+    date: String,
+
+    #[command(flatten)]
+    selection: LineSelectionArgs,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct VehiclesArgs {
+    /// Line id (e.g. ATB:Line:2_12), or a public line code to search for
+    #[arg(long)]
+    line: String,
+
+    /// Only match lines from this codespace or authority (e.g. ATB or Ruter)
+    #[arg(long)]
+    region: Option<String>,
+
+    #[command(flatten)]
+    selection: LineSelectionArgs,
+
+    /// Output format: text, json or ndjson
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct FavArgs {
+    #[command(subcommand)]
+    action: FavAction,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum FavAction {
+    /// Search for a stop or address and save it as @alias
+    Add(FavAddArgs),
+    /// List saved favourites
+    List,
+    /// Forget a saved favourite
+    Remove(FavRemoveArgs),
+}
+
+#[derive(Args, Debug)]
+struct FavAddArgs {
+    /// Name to save it under, used as @alias in --stop, --from and --to
+    alias: String,
+
+    /// Stop or street address to search for
+    #[arg(required_unless_present = "id", conflicts_with = "id")]
+    query: Option<String>,
+
+    /// Save this stop place id instead of searching
+    #[arg(long)]
+    id: Option<String>,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
+}
+
+#[derive(Args, Debug)]
+struct FavRemoveArgs {
+    alias: String,
 }
 
 #[derive(clap::Subcommand, Debug)]
 enum Action {
     Departure(DepartureArgs),
     Trip(TripArgs),
+    /// Stops and departures close to a coordinate
+    Nearby(NearbyArgs),
+    /// Every stop on a single service journey and where the vehicle is
+    Journey(JourneyArgs),
+    /// Information about a stop from the stop register
+    Stop(StopArgs),
+    /// Save stops and addresses to use as @alias
+    Fav(FavArgs),
+    /// Operator, stops and timetable of a line
+    Line(LineArgs),
+    /// Where the vehicles on a line are right now
+    Vehicles(VehiclesArgs),
 }
 
-fn print_choices(features: &[Feature]) {
-    for (i, feature) in features.iter().enumerate() {
-        println!(
-            "\x1b[32m{}\x1b[0m - \x1b[1m{}\x1b[0m ({} - {})",
-            i + 1,
-            feature.properties.name,
-            feature.properties.locality,
-            feature.properties.county
-        );
+/// Interactive prompts go to stderr when stdout is reserved for JSON.
+fn prompt_output(format: Format) -> Box<dyn Write> {
+    match format {
+        Format::Text => Box::new(io::stdout()),
+        Format::Json | Format::Ndjson => Box::new(io::stderr()),
     }
 }
 
-fn validate_choice(mut input: String, max_value: usize) -> usize {
-    loop {
-        match input.parse::<usize>() {
-            Ok(value) if value > 0 && value <= max_value => break value,
-            _ => {
-                print!(
-                    "\x1b[31mX\x1b[0m Invalid stop - pick another one (1 - {}): \x1b[1;36m",
-                    max_value
-                );
-                let _ = std::io::stdout().flush();
-                input.clear();
-                get_user_input(&mut input);
-                print!("\x1b[0m");
-            }
+/// A stop place id, with a display label when it came from the geocoder.
+struct ResolvedStop {
+    id: String,
+    label: Option<String>,
+}
+
+async fn resolve_stop(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    query: Option<&str>,
+    id: Option<&str>,
+    heading: Option<&str>,
+    selection: (Selection, &str),
+    format: Format,
+) -> Result<ResolvedStop, BusterminalError> {
+    let (Some(query), None) = (query, id) else {
+        return Ok(ResolvedStop {
+            id: id.unwrap_or_default().to_string(),
+            label: None,
+        });
+    };
+
+    if let Some(alias) = config::favourite_alias(query) {
+        let favourite = load_config(config)?.favourite(alias)?.clone();
+
+        if !favourite.is_stop() {
+            return Err(BusterminalError::InvalidConfig(format!(
+                "@{} is a street address, not a stop",
+                alias
+            )));
         }
+
+        return Ok(ResolvedStop {
+            id: favourite.id,
+            label: Some(favourite.name),
+        });
+    }
+
+    let feature = search(client, query, false, heading, selection, format).await?;
+
+    Ok(ResolvedStop {
+        id: feature.properties.id.clone(),
+        label: Some(feature_label(&feature)),
+    })
+}
+
+/// Where a trip starts or ends, with a label for messages.
+struct ResolvedPlace {
+    location: Location,
+    label: String,
+}
+
+/// Resolves a stop place id, a favourite, `"lat,lon"` coordinates or a search
+/// for a stop or street address.
+async fn resolve_place(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    query: Option<&str>,
+    id: Option<&str>,
+    heading: Option<&str>,
+    selection: (Selection, &str),
+    format: Format,
+) -> Result<ResolvedPlace, BusterminalError> {
+    let (Some(query), None) = (query, id) else {
+        let id = id.unwrap_or_default();
+
+        return Ok(ResolvedPlace {
+            location: Location::from(id),
+            label: id.to_string(),
+        });
+    };
+
+    if let Some(alias) = config::favourite_alias(query) {
+        let favourite = load_config(config)?.favourite(alias)?.clone();
+
+        return Ok(ResolvedPlace {
+            location: favourite.location(),
+            label: favourite.name,
+        });
+    }
+
+    if let Some(location) = Location::parse_coordinates(query) {
+        return Ok(ResolvedPlace {
+            location,
+            label: query.to_string(),
+        });
     }
+
+    let feature = search(client, query, true, heading, selection, format).await?;
+
+    Ok(ResolvedPlace {
+        location: feature.location(),
+        label: feature_label(&feature),
+    })
 }
 
-fn get_user_input(input: &mut String) {
-    io::stdin()
-        .read_line(input)
-        .expect("Did not enter a correct string");
+/// Searches for stops, or stops and street addresses, and settles on one
+/// match. A match chosen at the prompt is remembered in the cache so the same
+/// search does not ask again.
+async fn search(
+    client: &EnTurClient,
+    query: &str,
+    addresses: bool,
+    heading: Option<&str>,
+    (selection, prefix): (Selection, &str),
+    format: Format,
+) -> Result<Feature, BusterminalError> {
+    let matches = if addresses { &PLACES } else { &STOPS };
+    let key = format!("{}:{}", matches.plural, query);
+    let prompt = selection == Selection::Prompt;
+
+    if prompt
+        && let Some(feature) = client
+            .cache()
+            .and_then(|cache| cache.get::<Feature>("choices", &key))
+    {
+        return Ok(feature);
+    }
 
-    *input = input.trim().to_string();
+    let geo = if addresses {
+        client.get_autocomplete_place(query).await?
+    } else {
+        client.get_autocomplete_stop_name(query).await?
+    };
+    let feature = select(
+        query,
+        matches,
+        &geo.features,
+        heading,
+        (selection, prefix),
+        format,
+    )?
+    .clone();
+
+    if prompt
+        && geo.features.len() > 1
+        && let Some(cache) = client.cache()
+    {
+        cache.put("choices", &key, &feature);
+    }
+
+    Ok(feature)
 }
 
-fn print_departures(departures: &[EstimatedCall]) {
-    for call in departures {
-        let Ok(expected_departure) = DateTime::parse_from_rfc3339(&call.expectedDepartureTime)
-        else {
-            continue;
+/// What a search matched, named for prompts and error messages.
+struct Matches<T: 'static> {
+    /// One match, e.g. `stop`.
+    noun: &'static str,
+    /// Several matches, e.g. `stops`.
+    plural: &'static str,
+    /// How else to settle on one, after the pick flags.
+    otherwise: &'static str,
+    label: fn(&T) -> String,
+}
+
+const STOPS: Matches<Feature> = Matches {
+    noun: "stop",
+    plural: "stops",
+    otherwise: ", or give the stop place id",
+    label: render::feature_choice,
+};
+
+const PLACES: Matches<Feature> = Matches {
+    noun: "stop",
+    plural: "places",
+    otherwise: ", or give the stop place id",
+    label: render::feature_choice,
+};
+
+const LINES: Matches<LineInfo> = Matches {
+    noun: "line",
+    plural: "lines",
+    otherwise: ", narrow the search with --region or give the line id",
+    label: render::line_choice,
+};
+
+/// Settles on one of the `items` a search for `query` matched, refusing to
+/// prompt when stdin is not a terminal. The selection comes with the prefix of
+/// the flags that made it, e.g. `from-`, to name them in messages.
+fn select<'a, T>(
+    query: &str,
+    matches: &Matches<T>,
+    items: &'a [T],
+    heading: Option<&str>,
+    (selection, prefix): (Selection, &str),
+    format: Format,
+) -> Result<&'a T, BusterminalError> {
+    if selection == Selection::Prompt && items.len() > 1 && !io::stdin().is_terminal() {
+        render::print_choices(&mut io::stderr(), items, matches.label)?;
+
+        return Err(BusterminalError::InvalidConfig(format!(
+            "'{}' matches {} {} and stdin is not a terminal. Choose one with --{}pick N or --{}first{}",
+            query,
+            items.len(),
+            matches.plural,
+            prefix,
+            prefix,
+            matches.otherwise
+        )));
+    }
+
+    prompt::select(
+        &mut io::stdin().lock(),
+        &mut prompt_output(format),
+        heading,
+        items,
+        matches.label,
+        matches.noun,
+        selection,
+    )
+}
+
+fn config_path(path: Option<&Path>) -> Result<&Path, BusterminalError> {
+    path.ok_or_else(|| {
+        BusterminalError::InvalidConfig(String::from(
+            "Could not find a config directory. Set --config or BUSTERMINAL_CONFIG",
+        ))
+    })
+}
+
+fn load_config(path: Option<&Path>) -> Result<Config, BusterminalError> {
+    Config::load(config_path(path)?)
+}
+
+fn feature_label(feature: &Feature) -> String {
+    format!(
+        "{} ({} - {})",
+        feature.properties.name, feature.properties.locality, feature.properties.county
+    )
+}
+
+async fn departure(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    args: &DepartureArgs,
+) -> Result<(), BusterminalError> {
+    let text = args.format == Format::Text;
+    let options = args.options()?;
+
+    if args.watch && !text {
+        return Err(BusterminalError::InvalidConfig(String::from(
+            "--watch can only be used with --format text",
+        )));
+    }
+
+    if let (true, Some(query)) = (text, &args.stop)
+        && config::favourite_alias(query).is_none()
+    {
+        println!("Searching for \x1b[32;1m{}\x1b[0m", query);
+        println!();
+    }
+
+    let stop = resolve_stop(
+        client,
+        config,
+        args.stop.as_deref(),
+        args.stop_id.as_deref(),
+        None,
+        (args.selection.selection(), ""),
+        args.format,
+    )
+    .await?;
+
+    let stopplace = client
+        .get_stop_place(&stop.id, &options, &args.filter())
+        .await?;
+
+    if args.watch {
+        return watch::watch_departures(
+            client,
+            stopplace,
+            &options,
+            &args.filter(),
+            args.interval,
+            &mut io::stdout(),
+        )
+        .await;
+    }
+
+    if let Some(follow) = args.follow {
+        let call = follow
+            .checked_sub(1)
+            .and_then(|i| stopplace.estimatedCalls.get(i))
+            .ok_or_else(|| {
+                BusterminalError::InvalidConfig(format!(
+                    "Cannot follow departure {}, there are only {} departures (1 - {})",
+                    follow,
+                    stopplace.estimatedCalls.len(),
+                    stopplace.estimatedCalls.len()
+                ))
+            })?;
+
+        let Some(service_journey) = &call.serviceJourney else {
+            return Err(BusterminalError::NoMatch(format!(
+                "Departure {} has no service journey to follow",
+                follow
+            )));
         };
 
-        let now = chrono::offset::Local::now();
-        let arrives_in_minutes: i64 = expected_departure.signed_duration_since(now).num_minutes();
-        let expected_departure_formatted = expected_departure.format("%H:%M");
+        let journey = client.journey(&service_journey.id, &call.date).await?;
 
-        if let Some(service_journey) = &call.serviceJourney {
-            print!(
-                "\x1b[97;42;1m {} \x1b[0m",
-                service_journey.journeyPattern.line.publicCode
-            );
+        if text {
+            println!();
         }
 
-        println!(" {}", call.destinationDisplay.frontText);
+        args.format
+            .renderer()
+            .journey(&mut io::stdout().lock(), &journey)?;
 
-        if arrives_in_minutes > 10 {
-            println!(" \x1b[1m{}\x1b[0m", expected_departure_formatted);
+        return Ok(());
+    }
+
+    if text {
+        println!();
+        println!("----------------------------------");
+        println!();
+        let heading = if options.arrivals {
+            "Arrivals at"
         } else {
-            println!(
-                " \x1b[1m{:?} min\x1b[0m ({})",
-                arrives_in_minutes, expected_departure_formatted
-            );
+            "Departures for"
+        };
+        println!(
+            "\x1b[1m{} \x1b[4m{}\x1b[0m",
+            heading,
+            stop.label.as_deref().unwrap_or(&stopplace.name)
+        );
+
+        if let Some(start_time) = options.start_time {
+            println!("\x1b[1mFrom {}\x1b[0m", start_time.format("%a %d.%m %H:%M"));
         }
 
         println!();
     }
-}
 
-async fn departure(client: &EnTurClient, args: &DepartureArgs) {
-    println!("Searching for \x1b[32;1m{}\x1b[0m", args.stop);
-    println!();
+    if stopplace.estimatedCalls.is_empty() {
+        let message = if options.arrivals {
+            format!("No arrivals at {}", stopplace.name)
+        } else {
+            format!("No departures from {}", stopplace.name)
+        };
 
-    let Ok(geo_response) = client.get_autocomplete_stop_name(&args.stop).await else {
-        println!("Could not find any stops using query: {}", args.stop);
-        return;
-    };
+        return Err(BusterminalError::NoMatch(message));
+    }
 
-    let Ok(geo) = serde_json::from_str::<Geocode>(&geo_response) else {
-        // Could not parse response - panic/noop
-        return;
-    };
+    let renderer = args.format.renderer();
+    if let Some(group_by) = args.group_by {
+        let groups = departure::group_departures(
+            &stopplace.estimatedCalls,
+            group_by,
+            args.per_group as usize,
+        );
+        renderer.departure_groups(&mut io::stdout().lock(), &stopplace, &groups)?;
+    } else if options.arrivals {
+        renderer.arrivals(&mut io::stdout().lock(), &stopplace)?;
+    } else {
+        renderer.departures(&mut io::stdout().lock(), &stopplace)?;
+    }
 
-    let mut input: String = String::new();
+    Ok(())
+}
 
-    if geo.features.len() > 1 {
-        print_choices(&geo.features);
+async fn trip(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    args: &TripArgs,
+) -> Result<(), BusterminalError> {
+    let options = args.options()?;
+
+    if args.browse && args.format != Format::Text {
+        return Err(BusterminalError::InvalidConfig(String::from(
+            "--browse can only be used with --format text",
+        )));
+    }
 
+    if args.browse && !io::stdin().is_terminal() {
+        return Err(BusterminalError::InvalidConfig(String::from(
+            "--browse reads n and p from the terminal, but stdin is not a terminal",
+        )));
+    }
+
+    let from = resolve_place(
+        client,
+        config,
+        args.from.as_deref(),
+        args.from_id.as_deref(),
+        Some("Travel from"),
+        (selection(args.from_pick, args.from_first), "from-"),
+        args.format,
+    )
+    .await?;
+    let to = resolve_place(
+        client,
+        config,
+        args.to.as_deref(),
+        args.to_id.as_deref(),
+        Some("Travel to"),
+        (selection(args.to_pick, args.to_first), "to-"),
+        args.format,
+    )
+    .await?;
+
+    let mut trip: Trip = client
+        .plan_trip(from.location.clone(), to.location.clone(), &options)
+        .await?
+        .trip;
+
+    for paging in std::iter::repeat_n(Paging::Next, args.next.into())
+        .chain(std::iter::repeat_n(Paging::Previous, args.previous.into()))
+    {
+        match turn_page(
+            client,
+            &from.location,
+            &to.location,
+            &options,
+            &trip,
+            paging,
+        )
+        .await?
+        {
+            Some(page) => trip = page,
+            None => break,
+        }
+    }
+
+    if args.format == Format::Text {
         println!();
-        println!();
-        print!(
-            "\x1b[32m?\x1b[0m Which stop (1 - {}): \x1b[1;36m",
-            geo.features.len()
-        );
 
-        get_user_input(&mut input);
-        print!("\x1b[0m");
-    } else {
-        input = String::from("1");
+        if let Some(date_time) = options.date_time {
+            let label = if options.arrive_by {
+                "Arriving by"
+            } else {
+                "Leaving at"
+            };
+            println!(
+                "\x1b[1m{} {}\x1b[0m",
+                label,
+                date_time.format("%a %d.%m %H:%M")
+            );
+            println!();
+        }
     }
 
-    let input = validate_choice(input, geo.features.len());
+    if trip.tripPatterns.is_empty() {
+        return Err(BusterminalError::NoMatch(format!(
+            "No trips found from {} to {}",
+            from.label, to.label
+        )));
+    }
 
-    println!();
-    println!("----------------------------------");
-    println!();
-    println!(
-        "\x1b[1mDepartures for \x1b[4m{} ({} - {})\x1b[0m",
-        geo.features[input - 1].properties.name,
-        geo.features[input - 1].properties.locality,
-        geo.features[input - 1].properties.county,
-    );
-    println!();
+    let renderer = args.format.renderer();
+    renderer.trip_patterns(&mut io::stdout().lock(), &trip.tripPatterns)?;
 
-    let feature: &Feature = &geo.features[input - 1];
-    let now: String = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    if !args.browse {
+        return Ok(());
+    }
 
-    let Ok(stopplace_response) = client.get_stop_place(&feature.properties.id, &now).await else {
-        println!("Could not get any departures. Please try again later.");
-        return;
-    };
+    loop {
+        let paging = prompt::choose_page(
+            &mut io::stdin().lock(),
+            &mut io::stdout(),
+            trip.nextPageCursor.is_some(),
+            trip.previousPageCursor.is_some(),
+        )?;
+
+        let Some(page) = turn_page(
+            client,
+            &from.location,
+            &to.location,
+            &options,
+            &trip,
+            paging,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
 
-    if let Ok(stopplace) = serde_json::from_str::<Wrapper<StopPlaceResponse>>(&stopplace_response) {
-        print_departures(&stopplace.data.stopPlace.estimatedCalls);
+        trip = page;
+
+        println!();
+        renderer.trip_patterns(&mut io::stdout().lock(), &trip.tripPatterns)?;
     }
 }
 
-async fn trip(client: &EnTurClient, args: &TripArgs) {
-    let Ok(from_response) = client.get_autocomplete_stop_name(&args.from).await else {
-        println!("Could not find any stops using query: {}", args.from);
-        return;
+/// Fetches the page of trips after or before `trip`, or `None` when there is
+/// no such page.
+async fn turn_page(
+    client: &EnTurClient,
+    from: &Location,
+    to: &Location,
+    options: &TripOptions,
+    trip: &Trip,
+    paging: Paging,
+) -> Result<Option<Trip>, BusterminalError> {
+    let cursor = match paging {
+        Paging::Next => &trip.nextPageCursor,
+        Paging::Previous => &trip.previousPageCursor,
+        Paging::Quit => return Ok(None),
     };
 
-    let Ok(from) = serde_json::from_str::<Geocode>(&from_response) else {
-        // Could not parse response - panic/noop
-        return;
+    let Some(cursor) = cursor else {
+        return Ok(None);
     };
 
-    let Ok(to_response) = client.get_autocomplete_stop_name(&args.to).await else {
-        println!("Could not find any stops using query: {}", args.to);
-        return;
-    };
-
-    let Ok(to) = serde_json::from_str::<Geocode>(&to_response) else {
-        // Could not parse response - panic/noop
-        return;
-    };
+    let response = client
+        .plan_trip(from.clone(), to.clone(), &options.page(cursor))
+        .await?;
 
-    let false = from.features.is_empty() else {
-        println!("\x1b[31mX\x1b[0m Invalid stop: {} \x1b[1;36m", &args.to);
-        return;
-    };
+    Ok(Some(response.trip))
+}
 
-    let false = to.features.is_empty() else {
-        println!("\x1b[31mX\x1b[0m Invalid stop: {} \x1b[1;36m", &args.to);
-        return;
-    };
+async fn nearby(client: &EnTurClient, args: &NearbyArgs) -> Result<(), BusterminalError> {
+    let now: String = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
-    let mut from_input: String = String::new();
-    let mut to_input: String = String::new();
+    let mut stop_places = client
+        .get_nearby_stop_places(args.lat, args.lon, args.radius, &now)
+        .await?;
 
-    if from.features.len() > 1 {
-        println!("\x1b[1mTravel from\x1b[0m");
-        print_choices(&from.features);
+    if stop_places.is_empty() {
+        return Err(BusterminalError::NoMatch(format!(
+            "No stops within {} m of {},{}",
+            args.radius, args.lat, args.lon
+        )));
+    }
 
-        println!();
-        println!();
-        print!(
-            "\x1b[32m?\x1b[0m Which stop (1 - {}): \x1b[1;36m",
-            from.features.len()
+    let mut departures: Vec<EstimatedCall> = stop_places
+        .iter_mut()
+        .flat_map(|nearby| std::mem::take(&mut nearby.place.estimatedCalls))
+        .collect();
+    // Calls without a readable expected time go last instead of first.
+    departures.sort_by_key(|call| {
+        let departure = call.expected_departure();
+        (departure.is_none(), departure)
+    });
+
+    if args.format == Format::Text {
+        println!(
+            "\x1b[1mStops within {} m of \x1b[4m{},{}\x1b[0m",
+            args.radius, args.lat, args.lon
         );
-
-        get_user_input(&mut from_input);
-        print!("\x1b[0m");
-    } else {
-        from_input = "1".to_string();
+        println!();
     }
 
-    let from_input = validate_choice(from_input, from.features.len());
+    args.format
+        .renderer()
+        .nearby(&mut io::stdout().lock(), &stop_places, &departures)?;
 
-    if to.features.len() > 1 {
-        println!("\x1b[1mTravel to\x1b[0m");
-        print_choices(&to.features);
+    Ok(())
+}
 
-        println!();
-        println!();
-        print!(
-            "\x1b[32m?\x1b[0m Which stop (1 - {}): \x1b[1;36m",
-            to.features.len()
-        );
+async fn journey(client: &EnTurClient, args: &JourneyArgs) -> Result<(), BusterminalError> {
+    let date = time::parse_date(&args.date, Local::now().date_naive())
+        .map_err(BusterminalError::InvalidConfig)?;
+
+    let journey = client
+        .journey(
+            &args.service_journey_id,
+            &date.format("%Y-%m-%d").to_string(),
+        )
+        .await?;
+
+    args.format
+        .renderer()
+        .journey(&mut io::stdout().lock(), &journey)?;
 
-        get_user_input(&mut to_input);
-        print!("\x1b[0m");
+    Ok(())
+}
+
+async fn stop_info(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    args: &StopInfoArgs,
+) -> Result<(), BusterminalError> {
+    let (query, id) = if args.stop.contains(":StopPlace:") {
+        (None, Some(args.stop.as_str()))
     } else {
-        to_input = "1".to_string();
+        (Some(args.stop.as_str()), None)
+    };
+
+    let stop = resolve_stop(
+        client,
+        config,
+        query,
+        id,
+        None,
+        (args.selection.selection(), ""),
+        args.format,
+    )
+    .await?;
+    let info = client.get_stop_details(&stop.id).await?;
+
+    let searched = query.is_some_and(|query| config::favourite_alias(query).is_none());
+    if args.format == Format::Text && searched {
+        println!();
     }
 
-    let to_input = validate_choice(to_input, to.features.len());
+    args.format
+        .renderer()
+        .stop_info(&mut io::stdout().lock(), &info)?;
 
-    let Ok(trip_response) = client
-        .plan_trip(
-            &from.features[from_input - 1].properties.id,
-            &to.features[to_input - 1].properties.id,
+    Ok(())
+}
+
+async fn line(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    args: &LineArgs,
+) -> Result<(), BusterminalError> {
+    let date = time::parse_date(&args.date, Local::now().date_naive())
+        .map_err(BusterminalError::InvalidConfig)?;
+
+    let line_id = resolve_line(
+        client,
+        &args.line,
+        args.region.as_deref(),
+        args.selection.selection(),
+        args.format,
+    )
+    .await?;
+    let line = client.get_line(&line_id).await?;
+
+    let stop_place = if args.stop.is_some() || args.stop_id.is_some() {
+        let stop = resolve_stop(
+            client,
+            config,
+            args.stop.as_deref(),
+            args.stop_id.as_deref(),
+            None,
+            (selection(args.stop_pick, args.stop_first), "stop-"),
+            args.format,
         )
-        .await
-    else {
-        println!("Error retrieving trip response");
-        return;
+        .await?;
+
+        let options = DepartureOptions {
+            start_time: Local
+                .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                .earliest()
+                .map(|start| start.fixed_offset()),
+            window: Some(Duration::from_secs(24 * 60 * 60)),
+            limit: Some(500),
+            ..Default::default()
+        };
+        let filter = DepartureFilter {
+            lines: vec![line.id.clone()],
+            ..Default::default()
+        };
+
+        Some(client.get_stop_place(&stop.id, &options, &filter).await?)
+    } else {
+        None
     };
 
-    println!();
+    if args.format == Format::Text {
+        println!();
+    }
 
-    if let Ok(trip) = serde_json::from_str::<Wrapper<TripResponse>>(&trip_response) {
-        let patterns: Vec<TripPattern> = trip.data.trip.tripPatterns;
+    args.format
+        .renderer()
+        .line(&mut io::stdout().lock(), &line, stop_place.as_ref())?;
 
-        for pattern in patterns {
-            let duration = chrono::Duration::seconds(pattern.duration);
-            let hours = duration.num_hours();
-            let minutes = duration.num_minutes() - (hours * 60);
+    Ok(())
+}
 
-            print!("Travel time:");
-            if hours > 0 {
-                print!(" {} t", hours);
-            }
-            println!(" {} min", minutes);
-            println!();
+async fn vehicles(client: &EnTurClient, args: &VehiclesArgs) -> Result<(), BusterminalError> {
+    let line_id = resolve_line(
+        client,
+        &args.line,
+        args.region.as_deref(),
+        args.selection.selection(),
+        args.format,
+    )
+    .await?;
+    let vehicles = client.vehicles(&line_id).await?;
+
+    args.format
+        .renderer()
+        .vehicles(&mut io::stdout().lock(), &vehicles)?;
+
+    Ok(())
+}
 
-            for leg in &pattern.legs {
-                // println!("Mode: {}", leg.mode);
+/// A line id as given, or the id of the line found by public code.
+async fn resolve_line(
+    client: &EnTurClient,
+    query: &str,
+    region: Option<&str>,
+    selection: Selection,
+    format: Format,
+) -> Result<String, BusterminalError> {
+    if query.contains(":Line:") {
+        return Ok(query.to_string());
+    }
 
-                if let Some(from_estimated_call) = &leg.fromEstimatedCall {
-                    if let Ok(expected_departure) =
-                        DateTime::parse_from_rfc3339(&from_estimated_call.aimedDepartureTime)
-                    {
-                        print!("\x1b[1m{}\x1b[0m • ", expected_departure.format("%H:%M"));
-                    }
+    let lines = client.find_lines(query, region).await?;
 
-                    print!("{}", from_estimated_call.quay.name);
+    // A line whose public code is exactly the query wins over lines that
+    // merely start with or contain it, but only in place of the prompt:
+    // --pick and --first always choose from the list.
+    let exact: Vec<&LineInfo> = lines
+        .iter()
+        .filter(|line| line.public_code().eq_ignore_ascii_case(query))
+        .collect();
 
-                    if let Some(public_code) = &from_estimated_call.quay.publicCode {
-                        print!(" \x1b[1mSpor {}\x1b[0m ", public_code);
-                    }
+    if selection == Selection::Prompt
+        && let [line] = exact.as_slice()
+    {
+        return Ok(line.id.clone());
+    }
 
-                    println!();
-                }
+    Ok(
+        select(query, &LINES, &lines, None, (selection, ""), format)?
+            .id
+            .clone(),
+    )
+}
 
-                if leg.mode == Mode::foot {
-                    println!("      . ");
-                    println!(
-                        "      . Walk {} minutes",
-                        chrono::Duration::seconds(leg.duration).num_minutes()
-                    );
-                    println!("      . ");
-                } else {
-                    println!("      |");
-                    if let Some(line) = &leg.line {
-                        print!("      | \x1b[97;42m {} \x1b[0m ", line.publicCode);
-                    }
-
-                    if let Some(to_estimated_call) = &leg.toEstimatedCall {
-                        println!("{}", to_estimated_call.destinationDisplay.frontText);
-                    }
-
-                    println!(
-                        "      | {} min",
-                        chrono::Duration::seconds(leg.duration).num_minutes()
-                    );
-                    println!("      |");
-                    println!("      |");
-                }
+async fn fav(
+    client: &EnTurClient,
+    config: Option<&Path>,
+    action: &FavAction,
+) -> Result<(), BusterminalError> {
+    let path = config_path(config)?;
+    let mut settings = Config::load(path)?;
+
+    match action {
+        FavAction::Add(args) => {
+            let alias = config::favourite_alias(&args.alias).unwrap_or(&args.alias);
+
+            if alias.trim().is_empty() {
+                return Err(BusterminalError::InvalidConfig(String::from(
+                    "A favourite needs a name, e.g. `busterminal fav add home Tyholt`",
+                )));
+            }
 
-                if let Some(to_estimated_call) = &leg.toEstimatedCall {
-                    if let Ok(expected_departure) =
-                        DateTime::parse_from_rfc3339(&to_estimated_call.aimedDepartureTime)
-                    {
-                        print!("\x1b[1m{}\x1b[0m • ", expected_departure.format("%H:%M"));
-                    }
+            let (favourite, label) = match (&args.query, &args.id) {
+                (Some(query), _) => {
+                    let feature = search(
+                        client,
+                        query,
+                        true,
+                        Some("Save as"),
+                        (args.selection.selection(), ""),
+                        Format::Text,
+                    )
+                    .await?;
+
+                    (Favourite::from_feature(&feature), feature_label(&feature))
+                }
+                (None, id) => {
+                    let id = id.as_deref().unwrap_or_default();
+                    let stop_place = client.get_stop_info(id).await?;
+                    let favourite = Favourite {
+                        id: id.to_string(),
+                        name: stop_place.name().to_string(),
+                        latitude: None,
+                        longitude: None,
+                    };
+                    let label = favourite.name.clone();
+
+                    (favourite, label)
+                }
+            };
 
-                    print!("{} ", to_estimated_call.quay.name);
+            settings.favourites.insert(alias.to_string(), favourite);
+            settings.save(path)?;
 
-                    if let Some(public_code) = &to_estimated_call.quay.publicCode {
-                        print!(" \x1b[1mSpor {}\x1b[0m ", public_code);
-                    }
+            println!("Saved \x1b[1m@{}\x1b[0m as {}", alias, label);
+        }
+        FavAction::List => {
+            if settings.favourites.is_empty() {
+                println!("No favourites yet. Save one with `busterminal fav add <alias> <stop>`");
+                return Ok(());
+            }
 
-                    println!();
-                }
-                println!();
+            let width = settings
+                .favourites
+                .keys()
+                .map(|alias| alias.chars().count())
+                .max()
+                .unwrap_or(0);
+
+            for (alias, favourite) in &settings.favourites {
+                let place = match (favourite.latitude, favourite.longitude) {
+                    (Some(latitude), Some(longitude)) => format!("{},{}", latitude, longitude),
+                    _ => favourite.id.clone(),
+                };
+
+                println!(
+                    "\x1b[1m@{:<width$}\x1b[0m  {}  \x1b[2m{}\x1b[0m",
+                    alias, favourite.name, place
+                );
             }
+        }
+        FavAction::Remove(args) => {
+            let alias = config::favourite_alias(&args.alias).unwrap_or(&args.alias);
+            settings.favourite(alias)?;
+            settings.favourites.remove(alias);
+            settings.save(path)?;
 
-            println!(
-                "================================================================================"
-            );
-            println!();
+            println!("Removed \x1b[1m@{}\x1b[0m", alias);
         }
     }
+
+    Ok(())
 }
@@ -1,31 +1,255 @@
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 
-use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct DestinationDisplay {
     pub frontText: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct EstimatedCall {
     pub realtime: bool,
     pub aimedDepartureTime: String,
     pub expectedDepartureTime: String,
+    #[serde(default)]
+    pub aimedArrivalTime: Option<String>,
+    #[serde(default)]
+    pub expectedArrivalTime: Option<String>,
     pub date: String,
     pub forBoarding: bool,
+    #[serde(default)]
+    pub forAlighting: bool,
     pub destinationDisplay: DestinationDisplay,
     pub quay: Quay,
     pub serviceJourney: Option<ServiceJourney>,
+    #[serde(default)]
+    pub cancellation: bool,
+    #[serde(default)]
+    pub predictionInaccurate: bool,
+    #[serde(default)]
+    pub occupancyStatus: Option<OccupancyStatus>,
+    #[serde(default)]
+    pub situations: Vec<Situation>,
+}
+
+impl EstimatedCall {
+    pub fn aimed_departure(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.aimedDepartureTime).ok()
+    }
+
+    pub fn expected_departure(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.expectedDepartureTime).ok()
+    }
+
+    /// Whole minutes from `now` until the expected departure.
+    pub fn minutes_until_departure<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<i64> {
+        let expected = self.expected_departure()?;
+
+        Some(expected.signed_duration_since(now).num_minutes())
+    }
+
+    /// Minutes between the aimed and expected departure, positive when late.
+    pub fn delay_minutes(&self) -> Option<i64> {
+        let aimed = self.aimed_departure()?;
+        let expected = self.expected_departure()?;
+
+        Some(expected.signed_duration_since(aimed).num_minutes())
+    }
+
+    pub fn aimed_arrival(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.aimedArrivalTime.as_deref()?).ok()
+    }
+
+    pub fn expected_arrival(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.expectedArrivalTime.as_deref()?).ok()
+    }
+
+    /// Whole minutes from `now` until the expected arrival.
+    pub fn minutes_until_arrival<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<i64> {
+        let expected = self.expected_arrival()?;
+
+        Some(expected.signed_duration_since(now).num_minutes())
+    }
+
+    /// Minutes between the aimed and expected arrival, positive when late.
+    pub fn arrival_delay_minutes(&self) -> Option<i64> {
+        let aimed = self.aimed_arrival()?;
+        let expected = self.expected_arrival()?;
+
+        Some(expected.signed_duration_since(aimed).num_minutes())
+    }
+
+    /// The first stop of the service journey, where the vehicle is coming
+    /// from.
+    pub fn origin(&self) -> Option<&Quay> {
+        self.serviceJourney
+            .as_ref()
+            .and_then(|service_journey| service_journey.origin.first())
+    }
+
+    /// Identifies the same call across refreshes of a departure board.
+    pub fn key(&self) -> String {
+        match &self.serviceJourney {
+            Some(service_journey) => format!("{}/{}", service_journey.id, self.date),
+            None => format!(
+                "{}/{}/{}",
+                self.quay.id, self.aimedDepartureTime, self.destinationDisplay.frontText
+            ),
+        }
+    }
+
+    pub fn line(&self) -> Option<&Line> {
+        self.serviceJourney
+            .as_ref()
+            .map(|service_journey| &service_journey.journeyPattern.line)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum OccupancyStatus {
+    noData,
+    empty,
+    manySeatsAvailable,
+    fewSeatsAvailable,
+    standingRoomOnly,
+    crushedStandingRoomOnly,
+    full,
+    notAcceptingPassengers,
+}
+
+impl fmt::Display for OccupancyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OccupancyStatus::noData => write!(f, "No data"),
+            OccupancyStatus::empty => write!(f, "Empty"),
+            OccupancyStatus::manySeatsAvailable => write!(f, "Many seats available"),
+            OccupancyStatus::fewSeatsAvailable => write!(f, "Few seats available"),
+            OccupancyStatus::standingRoomOnly => write!(f, "Standing room only"),
+            OccupancyStatus::crushedStandingRoomOnly => write!(f, "Crowded"),
+            OccupancyStatus::full => write!(f, "Full"),
+            OccupancyStatus::notAcceptingPassengers => write!(f, "Not accepting passengers"),
+        }
+    }
+}
+
+/// A service alert (SIRI-SX situation) affecting a stop, line or journey.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Situation {
+    pub id: String,
+    pub situationNumber: Option<String>,
+    pub severity: Option<Severity>,
+    #[serde(default)]
+    pub summary: Vec<MultilingualString>,
+    #[serde(default)]
+    pub description: Vec<MultilingualString>,
+    #[serde(default)]
+    pub advice: Vec<MultilingualString>,
+    pub validityPeriod: Option<ValidityPeriod>,
+}
+
+impl Situation {
+    pub fn summary_text(&self) -> Option<&str> {
+        MultilingualString::preferred(&self.summary)
+    }
+
+    pub fn description_text(&self) -> Option<&str> {
+        MultilingualString::preferred(&self.description)
+    }
+
+    pub fn advice_text(&self) -> Option<&str> {
+        MultilingualString::preferred(&self.advice)
+    }
+}
+
+/// Removes situations already seen earlier in the list, keeping the order.
+/// The same alert is often attached both to a stop and to a line.
+pub fn dedup_situations(situations: &mut Vec<Situation>) {
+    let mut seen = HashSet::new();
+
+    situations.retain(|situation| seen.insert(situation.id.clone()));
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MultilingualString {
+    pub language: Option<String>,
+    pub value: String,
+}
+
+impl MultilingualString {
+    /// The English text when there is one, otherwise the first translation.
+    pub fn preferred(texts: &[MultilingualString]) -> Option<&str> {
+        texts
+            .iter()
+            .find(|text| text.language.as_deref() == Some("en"))
+            .or_else(|| texts.first())
+            .map(|text| text.value.as_str())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ValidityPeriod {
+    pub startTime: Option<String>,
+    pub endTime: Option<String>,
+}
+
+impl ValidityPeriod {
+    pub fn start(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.startTime.as_deref()?).ok()
+    }
+
+    pub fn end(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.endTime.as_deref()?).ok()
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum Severity {
+    unknown,
+    noImpact,
+    verySlight,
+    slight,
+    normal,
+    severe,
+    verySevere,
+    undefined,
+}
+
+impl Severity {
+    pub fn is_severe(&self) -> bool {
+        matches!(self, Severity::severe | Severity::verySevere)
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::unknown => write!(f, "Unknown"),
+            Severity::noImpact => write!(f, "No impact"),
+            Severity::verySlight => write!(f, "Very slight"),
+            Severity::slight => write!(f, "Slight"),
+            Severity::normal => write!(f, "Normal"),
+            Severity::severe => write!(f, "Severe"),
+            Severity::verySevere => write!(f, "Very severe"),
+            Severity::undefined => write!(f, "Undefined"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
 pub struct JourneyPattern {
     pub line: Line,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Line {
     pub id: String,
     pub publicCode: String,
@@ -33,7 +257,7 @@ pub struct Line {
     pub transportMode: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Quay {
     pub id: String,
     pub name: String,
@@ -41,71 +265,574 @@ pub struct Quay {
     pub description: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct ServiceJourney {
     pub id: String,
     pub journeyPattern: JourneyPattern,
+    /// The first quay of the journey. Only fetched for stop place calls.
+    #[serde(default)]
+    pub origin: Vec<Quay>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ServiceJourneyResponse {
+    pub serviceJourney: Option<Journey>,
+}
+
+/// One run of a vehicle along a line, with every stop it calls at.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Journey {
+    pub id: String,
+    pub line: Line,
+    pub estimatedCalls: Vec<JourneyCall>,
+}
+
+/// Where a vehicle is along its journey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VehiclePosition {
+    NotStarted,
+    /// At the call with this index.
+    AtStop(usize),
+    /// Left the call with this index, on the way to the next.
+    Departed(usize),
+    Finished,
+}
+
+impl VehiclePosition {
+    /// Whether the vehicle has left the call with this index behind.
+    pub fn has_passed(self, index: usize) -> bool {
+        match self {
+            VehiclePosition::NotStarted => false,
+            VehiclePosition::AtStop(at) => index < at,
+            VehiclePosition::Departed(from) => index <= from,
+            VehiclePosition::Finished => true,
+        }
+    }
+}
+
+impl Journey {
+    /// Works out where the vehicle is from actual times, or from expected
+    /// times for calls without one. `None` when there is no realtime data.
+    pub fn vehicle_position<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<VehiclePosition> {
+        if !self.estimatedCalls.iter().any(|call| call.realtime) {
+            return None;
+        }
+
+        let Some(index) = self
+            .estimatedCalls
+            .iter()
+            .rposition(|call| call.has_arrived(now))
+        else {
+            return Some(VehiclePosition::NotStarted);
+        };
+
+        if index + 1 == self.estimatedCalls.len() {
+            Some(VehiclePosition::Finished)
+        } else if self.estimatedCalls[index].has_departed(now) {
+            Some(VehiclePosition::Departed(index))
+        } else {
+            Some(VehiclePosition::AtStop(index))
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct JourneyCall {
+    pub realtime: bool,
+    #[serde(default)]
+    pub cancellation: bool,
+    pub aimedArrivalTime: String,
+    pub expectedArrivalTime: String,
+    pub actualArrivalTime: Option<String>,
+    pub aimedDepartureTime: String,
+    pub expectedDepartureTime: String,
+    pub actualDepartureTime: Option<String>,
+    pub destinationDisplay: Option<DestinationDisplay>,
+    pub quay: Quay,
+}
+
+impl JourneyCall {
+    pub fn aimed_departure(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.aimedDepartureTime).ok()
+    }
+
+    pub fn expected_departure(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.expectedDepartureTime).ok()
+    }
+
+    pub fn actual_departure(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.actualDepartureTime.as_deref()?).ok()
+    }
+
+    pub fn expected_arrival(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.expectedArrivalTime).ok()
+    }
+
+    pub fn has_arrived<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> bool {
+        self.actualArrivalTime.is_some()
+            || self.actualDepartureTime.is_some()
+            || self
+                .expected_arrival()
+                .is_some_and(|arrival| arrival.signed_duration_since(now).num_seconds() <= 0)
+    }
+
+    pub fn has_departed<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> bool {
+        self.actualDepartureTime.is_some()
+            || self
+                .expected_departure()
+                .is_some_and(|departure| departure.signed_duration_since(now).num_seconds() < 0)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
 pub struct StopPlace {
     pub id: String,
     pub name: String,
     pub estimatedCalls: Vec<EstimatedCall>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct StopPlaceResponse {
-    pub stopPlace: StopPlace,
+    pub stopPlace: Option<StopPlace>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NearestResponse {
+    pub nearest: Nearest,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Nearest {
+    pub edges: Vec<NearestEdge>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NearestEdge {
+    pub node: NearbyStopPlace,
+}
+
+/// A stop place close to a coordinate, with its next departures.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NearbyStopPlace {
+    /// Distance in meters.
+    pub distance: f64,
+    pub place: StopPlace,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LineResponse {
+    pub line: Option<LineInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LinesResponse {
+    pub lines: Vec<LineInfo>,
+}
+
+/// A line as looked up by id or public code. Journey patterns are only
+/// fetched when looking up a single line.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LineInfo {
+    pub id: String,
+    pub publicCode: Option<String>,
+    pub name: Option<String>,
+    pub transportMode: Option<String>,
+    pub authority: Option<Organisation>,
+    pub operator: Option<Organisation>,
+    #[serde(default)]
+    pub journeyPatterns: Vec<JourneyPatternInfo>,
+}
+
+impl LineInfo {
+    pub fn public_code(&self) -> &str {
+        self.publicCode.as_deref().unwrap_or_default()
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AuthoritiesResponse {
+    pub authorities: Vec<Organisation>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Organisation {
+    pub id: String,
+    pub name: String,
+}
+
+/// One route of a line: the quays it calls at, in order.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct JourneyPatternInfo {
+    pub id: String,
+    pub name: Option<String>,
+    /// `inbound`, `outbound`, `clockwise`, ...
+    pub directionType: Option<String>,
+    #[serde(default)]
+    pub quays: Vec<Quay>,
+}
+
+/// Vehicle positions types
+#[derive(Deserialize, Serialize, Debug)]
+pub struct VehiclesResponse {
+    #[serde(default)]
+    pub vehicles: Vec<Vehicle>,
+}
+
+/// The last reported position of a vehicle, from the vehicle positions API.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Vehicle {
+    pub vehicleId: Option<String>,
+    pub line: Option<VehicleLine>,
+    pub serviceJourney: Option<VehicleServiceJourney>,
+    pub destinationName: Option<String>,
+    /// `BUS`, `TRAM`, `RAIL`, ...
+    pub mode: Option<String>,
+    pub lastUpdated: String,
+    /// Seconds behind the timetable, negative when early.
+    pub delay: Option<f64>,
+    pub location: Option<VehicleLocation>,
+    /// Direction of travel in degrees.
+    pub bearing: Option<f64>,
+    pub monitoredCall: Option<MonitoredCall>,
+    /// The quay of `monitoredCall`, looked up in the journey planner.
+    #[serde(default)]
+    pub nextQuay: Option<Quay>,
+}
+
+impl Vehicle {
+    pub fn last_updated(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.lastUpdated).ok()
+    }
+
+    /// Whole seconds from the last position update until `now`.
+    pub fn seconds_since_update<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<i64> {
+        let updated = self.last_updated()?;
+
+        let age = -updated.signed_duration_since(now).num_seconds();
+
+        Some(age.max(0))
+    }
+
+    /// Delay rounded to whole minutes.
+    pub fn delay_minutes(&self) -> Option<i64> {
+        self.delay.map(|delay| (delay / 60.0).round() as i64)
+    }
+
+    /// The quay the vehicle is at or heading for, by id when its name is not
+    /// known.
+    pub fn next_stop(&self) -> Option<&str> {
+        self.nextQuay
+            .as_ref()
+            .map(|quay| quay.name.as_str())
+            .or_else(|| {
+                self.monitoredCall
+                    .as_ref()
+                    .and_then(|call| call.stopPointRef.as_deref())
+            })
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct VehicleLine {
+    pub lineRef: String,
+    pub publicCode: Option<String>,
+    pub lineName: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct VehicleServiceJourney {
+    pub id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct VehicleLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// The stop a vehicle is at, or the next one it calls at.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MonitoredCall {
+    /// Quay id, e.g. `NSR:Quay:73976`.
+    pub stopPointRef: Option<String>,
+    #[serde(default)]
+    pub vehicleAtStop: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct QuaysResponse {
+    #[serde(default)]
+    pub quays: Vec<Quay>,
+}
+
+/// StopRegister types
+#[derive(Deserialize, Serialize, Debug)]
+pub struct StopInfoResponse {
+    #[serde(default)]
+    pub stopPlace: Vec<StopPlaceInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct StopPlaceInfo {
+    pub id: String,
+    pub name: Option<Text>,
+    pub stopPlaceType: Option<String>,
+    pub transportMode: Option<String>,
+    pub geometry: Option<GeoJson>,
+    pub accessibilityAssessment: Option<AccessibilityAssessment>,
+    pub parentSiteRef: Option<SiteRef>,
+    #[serde(default)]
+    pub adjacentSites: Vec<SiteRef>,
+    #[serde(default)]
+    pub quays: Vec<QuayInfo>,
+    /// Stop places grouped under a multimodal parent.
+    #[serde(default)]
+    pub children: Vec<StopPlaceInfo>,
+}
+
+impl StopPlaceInfo {
+    pub fn name(&self) -> &str {
+        self.name
+            .as_ref()
+            .map_or(self.id.as_str(), |name| name.value.as_str())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct QuayInfo {
+    pub id: String,
+    pub publicCode: Option<String>,
+    /// Direction vehicles face when stopped, in degrees.
+    pub compassBearing: Option<f64>,
+    pub description: Option<Text>,
+    pub geometry: Option<GeoJson>,
+    pub accessibilityAssessment: Option<AccessibilityAssessment>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Text {
+    pub value: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SiteRef {
+    pub r#ref: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GeoJson {
+    pub coordinates: Coordinates,
+}
+
+impl GeoJson {
+    /// `(latitude, longitude)` of a point.
+    pub fn lat_lon(&self) -> Option<(f64, f64)> {
+        let [longitude, latitude] = match &self.coordinates {
+            Coordinates::Point(point) => *point,
+            Coordinates::Points(points) => *points.first()?,
+        };
+
+        Some((latitude, longitude))
+    }
+}
+
+/// The stop register nests the coordinates of a point in an extra list.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Coordinates {
+    Point([f64; 2]),
+    Points(Vec<[f64; 2]>),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AccessibilityAssessment {
+    pub limitations: Option<OneOrMany<Limitations>>,
+}
+
+impl AccessibilityAssessment {
+    pub fn limitations(&self) -> Option<&Limitations> {
+        match self.limitations.as_ref()? {
+            OneOrMany::One(limitations) => Some(limitations),
+            OneOrMany::Many(limitations) => limitations.first(),
+        }
+    }
+}
+
+/// Each value is `TRUE`, `FALSE`, `PARTIAL` or `UNKNOWN`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Limitations {
+    pub wheelchairAccess: Option<String>,
+    pub stepFreeAccess: Option<String>,
+    pub escalatorFreeAccess: Option<String>,
+    pub liftFreeAccess: Option<String>,
+    pub audibleSignalsAvailable: Option<String>,
+    pub visualSignsAvailable: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+/// A stop place with its parent and adjacent stop places looked up as well.
+#[derive(Serialize, Debug)]
+pub struct StopInfo {
+    pub stopPlace: StopPlaceInfo,
+    pub parent: Option<StopPlaceInfo>,
+    pub adjacent: Vec<StopPlaceInfo>,
 }
 
 /// Geocode types
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct GeocodeResponse {
     pub geocoding: Geocode,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Geocode {
     pub features: Vec<Feature>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Feature {
     pub geometry: Geometry,
     pub properties: Stop,
 }
 
-#[derive(Deserialize, Debug)]
+impl Feature {
+    /// Where to travel from or to: the stop place itself, or the coordinates
+    /// of an address.
+    pub fn location(&self) -> Location {
+        if self.properties.is_address() {
+            let [longitude, latitude] = self.geometry.coordinates;
+
+            Location::Coordinates {
+                latitude: latitude.into(),
+                longitude: longitude.into(),
+                name: Some(self.properties.name.clone()),
+            }
+        } else {
+            Location::Place(self.properties.id.clone())
+        }
+    }
+}
+
+/// GeoJSON order: longitude first.
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Geometry {
     pub coordinates: [f32; 2],
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Stop {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub locality: String,
+    #[serde(default)]
     pub county: String,
+    /// `venue` for stop places, `address` for street addresses.
+    #[serde(default)]
+    pub layer: Option<String>,
+    /// Kinds of stop, e.g. `railStation` or `onstreetBus`.
+    #[serde(default)]
+    pub category: Vec<String>,
+}
+
+impl Stop {
+    pub fn is_address(&self) -> bool {
+        self.layer.as_deref() == Some("address")
+    }
+}
+
+/// A place to travel from or to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    /// A stop place id such as `NSR:StopPlace:59872`.
+    Place(String),
+    Coordinates {
+        latitude: f64,
+        longitude: f64,
+        name: Option<String>,
+    },
+}
+
+impl Location {
+    /// Parses `"59.91,10.75"` (latitude first) as coordinates.
+    pub fn parse_coordinates(value: &str) -> Option<Self> {
+        let (latitude, longitude) = value.split_once(',')?;
+        let latitude: f64 = latitude.trim().parse().ok()?;
+        let longitude: f64 = longitude.trim().parse().ok()?;
+
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+
+        Some(Location::Coordinates {
+            latitude,
+            longitude,
+            name: None,
+        })
+    }
+}
+
+impl From<&str> for Location {
+    fn from(id: &str) -> Self {
+        Location::Place(id.to_string())
+    }
+}
+
+impl From<String> for Location {
+    fn from(id: String) -> Self {
+        Location::Place(id)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Place(id) => write!(f, "{}", id),
+            Location::Coordinates {
+                name: Some(name), ..
+            } => write!(f, "{}", name),
+            Location::Coordinates {
+                latitude,
+                longitude,
+                name: None,
+            } => write!(f, "{},{}", latitude, longitude),
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct TripResponse {
     pub trip: Trip,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Trip {
     pub tripPatterns: Vec<TripPattern>,
+    /// Pass as `TripOptions::page_cursor` for later connections.
+    #[serde(default)]
+    pub nextPageCursor: Option<String>,
+    /// Pass as `TripOptions::page_cursor` for earlier connections.
+    #[serde(default)]
+    pub previousPageCursor: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct TripPattern {
     pub duration: i64,
     pub walkDistance: f64,
     pub legs: Vec<Leg>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Leg {
     pub expectedStartTime: String,
     pub expectedEndTime: String,
@@ -113,11 +840,34 @@ pub struct Leg {
     pub mode: Mode,
     pub distance: f64,
     pub line: Option<Line>,
+    /// Where the leg starts; the only place known for a walk from an address
+    /// or a coordinate.
+    #[serde(default)]
+    pub fromPlace: Option<LegPlace>,
     pub fromEstimatedCall: Option<EstimatedCall>,
     pub toEstimatedCall: Option<EstimatedCall>,
+    #[serde(default)]
+    pub situations: Vec<Situation>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LegPlace {
+    /// `None` when the trip starts from a bare coordinate.
+    pub name: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl fmt::Display for LegPlace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "{},{}", self.latitude, self.longitude),
+        }
+    }
 }
 
-#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(non_camel_case_types)]
 pub enum Mode {
     air,
@@ -138,7 +888,53 @@ pub enum Mode {
     scooter,
 }
 
-use std::fmt;
+impl Mode {
+    pub const ALL: [Mode; 16] = [
+        Mode::air,
+        Mode::bicycle,
+        Mode::bus,
+        Mode::cableway,
+        Mode::water,
+        Mode::funicular,
+        Mode::lift,
+        Mode::rail,
+        Mode::metro,
+        Mode::tram,
+        Mode::trolleybus,
+        Mode::monorail,
+        Mode::coach,
+        Mode::foot,
+        Mode::car,
+        Mode::scooter,
+    ];
+
+    /// The name used for this mode in the Entur APIs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mode::air => "air",
+            Mode::bicycle => "bicycle",
+            Mode::bus => "bus",
+            Mode::cableway => "cableway",
+            Mode::water => "water",
+            Mode::funicular => "funicular",
+            Mode::lift => "lift",
+            Mode::rail => "rail",
+            Mode::metro => "metro",
+            Mode::tram => "tram",
+            Mode::trolleybus => "trolleybus",
+            Mode::monorail => "monorail",
+            Mode::coach => "coach",
+            Mode::foot => "foot",
+            Mode::car => "car",
+            Mode::scooter => "scooter",
+        }
+    }
+
+    /// Whether this is public transport rather than a way of getting to it.
+    pub fn is_transit(&self) -> bool {
+        !matches!(self, Mode::foot | Mode::bicycle | Mode::car | Mode::scooter)
+    }
+}
 
 impl fmt::Display for Mode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -162,3 +958,14 @@ impl fmt::Display for Mode {
         }
     }
 }
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Mode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| format!("unknown transport mode '{}'", value))
+    }
+}
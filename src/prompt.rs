@@ -0,0 +1,172 @@
+use std::io::{self, BufRead, Write};
+
+use crate::error::BusterminalError;
+use crate::render;
+
+/// How to settle on one of several matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// Ask the user on `input`.
+    Prompt,
+    /// Take the n-th match, counting from 1 like the prompt does.
+    Pick(usize),
+    /// Take the best match.
+    First,
+}
+
+/// Where to go after a page of trip patterns has been shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paging {
+    Next,
+    Previous,
+    Quit,
+}
+
+/// Asks whether to show later (`n`) or earlier (`p`) connections. Anything
+/// else, or the end of `input`, quits.
+pub fn choose_page(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    has_next: bool,
+    has_previous: bool,
+) -> io::Result<Paging> {
+    if !has_next && !has_previous {
+        return Ok(Paging::Quit);
+    }
+
+    let mut keys = Vec::new();
+    if has_next {
+        keys.push("n: later");
+    }
+    if has_previous {
+        keys.push("p: earlier");
+    }
+    keys.push("Enter: quit");
+
+    write!(out, "\x1b[32m?\x1b[0m {} \x1b[1;36m", keys.join(", "))?;
+    out.flush()?;
+
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    write!(out, "\x1b[0m")?;
+
+    Ok(match line.trim().to_lowercase().as_str() {
+        "n" if has_next => Paging::Next,
+        "p" if has_previous => Paging::Previous,
+        _ => Paging::Quit,
+    })
+}
+
+/// Picks one of `items` according to `selection`, prompting only for
+/// [`Selection::Prompt`] when there is more than one. `noun` names an item in
+/// messages, e.g. `stop`.
+pub fn select<'a, T>(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    heading: Option<&str>,
+    items: &'a [T],
+    label: impl Fn(&T) -> String,
+    noun: &str,
+    selection: Selection,
+) -> Result<&'a T, BusterminalError> {
+    match selection {
+        Selection::Prompt => Ok(choose(input, out, heading, items, label, noun)?),
+        Selection::First => items
+            .first()
+            .ok_or_else(|| BusterminalError::NoMatch(format!("No {}s to choose from", noun))),
+        Selection::Pick(choice) => match choice.checked_sub(1).and_then(|i| items.get(i)) {
+            Some(item) => Ok(item),
+            None => Err(BusterminalError::InvalidConfig(format!(
+                "Cannot pick {} {}, there are only {} to choose from (1 - {})",
+                noun,
+                choice,
+                items.len(),
+                items.len()
+            ))),
+        },
+    }
+}
+
+/// Lets the user pick one of `items`, each listed with its `label`. A single
+/// item is returned straight away without prompting.
+pub fn choose<'a, T>(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    heading: Option<&str>,
+    items: &'a [T],
+    label: impl Fn(&T) -> String,
+    noun: &str,
+) -> io::Result<&'a T> {
+    match items.len() {
+        0 => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("No {}s to choose from", noun),
+            ));
+        }
+        1 => return Ok(&items[0]),
+        _ => {}
+    }
+
+    if let Some(heading) = heading {
+        writeln!(out, "\x1b[1m{}\x1b[0m", heading)?;
+    }
+
+    render::print_choices(out, items, label)?;
+
+    writeln!(out)?;
+    writeln!(out)?;
+    write!(
+        out,
+        "\x1b[32m?\x1b[0m Which {} (1 - {}): \x1b[1;36m",
+        noun,
+        items.len()
+    )?;
+    out.flush()?;
+
+    let line = get_user_input(input, noun)?;
+    write!(out, "\x1b[0m")?;
+
+    let choice = validate_choice(input, out, line, items.len(), noun)?;
+
+    Ok(&items[choice - 1])
+}
+
+/// Parses `line` as a choice between 1 and `max_value`, asking again until a
+/// valid choice is entered.
+pub fn validate_choice(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    mut line: String,
+    max_value: usize,
+    noun: &str,
+) -> io::Result<usize> {
+    loop {
+        match line.parse::<usize>() {
+            Ok(value) if value > 0 && value <= max_value => break Ok(value),
+            _ => {
+                write!(
+                    out,
+                    "\x1b[31mX\x1b[0m Invalid {} - pick another one (1 - {}): \x1b[1;36m",
+                    noun, max_value
+                )?;
+                out.flush()?;
+                line = get_user_input(input, noun)?;
+                write!(out, "\x1b[0m")?;
+            }
+        }
+    }
+}
+
+fn get_user_input(input: &mut impl BufRead, noun: &str) -> io::Result<String> {
+    let mut line = String::new();
+
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("No {} was chosen", noun),
+        ));
+    }
+
+    Ok(line.trim().to_string())
+}
@@ -0,0 +1,117 @@
+//! Output of departures and trips, either as colored terminal text or as
+//! JSON for scripts.
+
+mod json;
+mod text;
+
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::departure::DepartureGroup;
+use crate::model::{
+    EstimatedCall, Journey, LineInfo, NearbyStopPlace, StopInfo, StopPlace, TripPattern, Vehicle,
+};
+
+pub use json::{
+    ArrivalRecord, DepartureRecord, JourneyCallRecord, JourneyPatternRecord, JourneyRecord,
+    LegRecord, LineRecord, LocationRecord, NearbyStopPlaceRecord, NextStopRecord, QuayInfoRecord,
+    RelatedStopPlaceRecord, StopInfoRecord, TripPatternRecord, VehicleLineRecord,
+    VehiclePositionRecord, VehicleRecord,
+};
+pub use text::{
+    feature_choice, format_arrival_time, format_call_time, format_departure_time,
+    format_validity_period, line_choice, print_arrivals, print_board, print_choices,
+    print_departure_groups, print_departures, print_journey, print_line, print_nearby_stop_places,
+    print_situations, print_stop_info, print_trip_patterns, print_vehicles,
+};
+
+pub trait Renderer {
+    fn departures(&self, out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()>;
+
+    /// The calls of a stop place fetched as arrivals.
+    fn arrivals(&self, out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()>;
+
+    /// The departures of a stop place split into sections.
+    fn departure_groups(
+        &self,
+        out: &mut dyn Write,
+        stop_place: &StopPlace,
+        groups: &[DepartureGroup],
+    ) -> io::Result<()>;
+
+    fn trip_patterns(&self, out: &mut dyn Write, patterns: &[TripPattern]) -> io::Result<()>;
+
+    /// Every call of a single service journey.
+    fn journey(&self, out: &mut dyn Write, journey: &Journey) -> io::Result<()>;
+
+    /// A line and its journey patterns, with the day's departures from
+    /// `stop_place` when one was chosen.
+    fn line(
+        &self,
+        out: &mut dyn Write,
+        line: &LineInfo,
+        stop_place: Option<&StopPlace>,
+    ) -> io::Result<()>;
+
+    /// The last reported positions of the vehicles on a line.
+    fn vehicles(&self, out: &mut dyn Write, vehicles: &[Vehicle]) -> io::Result<()>;
+
+    /// A stop place from the stop register.
+    fn stop_info(&self, out: &mut dyn Write, info: &StopInfo) -> io::Result<()>;
+
+    /// Stops close to a coordinate and their departures merged into one list.
+    fn nearby(
+        &self,
+        out: &mut dyn Write,
+        stop_places: &[NearbyStopPlace],
+        departures: &[EstimatedCall],
+    ) -> io::Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Colored text for a terminal.
+    #[default]
+    Text,
+    /// A single JSON document.
+    Json,
+    /// One JSON document per line, one line per departure or trip pattern.
+    Ndjson,
+}
+
+impl Format {
+    pub fn renderer(self) -> Box<dyn Renderer> {
+        match self {
+            Format::Text => Box::new(text::TextRenderer),
+            Format::Json => Box::new(json::JsonRenderer),
+            Format::Ndjson => Box::new(json::NdjsonRenderer),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            other => Err(format!(
+                "unknown format '{}', expected text, json or ndjson",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
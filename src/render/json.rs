@@ -0,0 +1,820 @@
+use std::io::{self, Write};
+
+use chrono::Local;
+use serde::Serialize;
+
+use super::Renderer;
+use crate::departure::DepartureGroup;
+use crate::model::{
+    AccessibilityAssessment, EstimatedCall, GeoJson, Journey, JourneyCall, JourneyPatternInfo, Leg,
+    Limitations, Line, LineInfo, Mode, NearbyStopPlace, OccupancyStatus, Organisation, Quay,
+    QuayInfo, Situation, StopInfo, StopPlace, StopPlaceInfo, TripPattern, Vehicle, VehicleLocation,
+    VehiclePosition,
+};
+
+/// A departure as written by `--format json` and `--format ndjson`.
+///
+/// Times are RFC 3339 strings as returned by Entur. `minutesUntilDeparture`
+/// is computed against the local clock when the output is written.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DepartureRecord<'a> {
+    pub line: Option<&'a Line>,
+    pub destination: &'a str,
+    pub quay: &'a Quay,
+    pub aimed_departure_time: &'a str,
+    pub expected_departure_time: &'a str,
+    pub minutes_until_departure: Option<i64>,
+    pub delay_minutes: Option<i64>,
+    pub realtime: bool,
+    pub cancelled: bool,
+    pub prediction_inaccurate: bool,
+    pub occupancy_status: Option<OccupancyStatus>,
+    pub service_journey_id: Option<&'a str>,
+    pub situations: &'a [Situation],
+}
+
+impl<'a> From<&'a EstimatedCall> for DepartureRecord<'a> {
+    fn from(call: &'a EstimatedCall) -> Self {
+        Self {
+            line: call.line(),
+            destination: &call.destinationDisplay.frontText,
+            quay: &call.quay,
+            aimed_departure_time: &call.aimedDepartureTime,
+            expected_departure_time: &call.expectedDepartureTime,
+            minutes_until_departure: call.minutes_until_departure(&Local::now()),
+            delay_minutes: call.delay_minutes(),
+            realtime: call.realtime,
+            cancelled: call.cancellation,
+            prediction_inaccurate: call.predictionInaccurate,
+            occupancy_status: call.occupancyStatus,
+            service_journey_id: call.serviceJourney.as_ref().map(|sj| sj.id.as_str()),
+            situations: &call.situations,
+        }
+    }
+}
+
+/// An arrival as written by `departure --arrivals` with `--format json` and
+/// `--format ndjson`. `origin` is the first quay of the service journey.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrivalRecord<'a> {
+    pub line: Option<&'a Line>,
+    pub origin: Option<&'a Quay>,
+    pub destination: &'a str,
+    pub quay: &'a Quay,
+    pub aimed_arrival_time: Option<&'a str>,
+    pub expected_arrival_time: Option<&'a str>,
+    pub minutes_until_arrival: Option<i64>,
+    pub delay_minutes: Option<i64>,
+    pub realtime: bool,
+    pub cancelled: bool,
+    pub prediction_inaccurate: bool,
+    pub occupancy_status: Option<OccupancyStatus>,
+    pub service_journey_id: Option<&'a str>,
+    pub situations: &'a [Situation],
+}
+
+impl<'a> From<&'a EstimatedCall> for ArrivalRecord<'a> {
+    fn from(call: &'a EstimatedCall) -> Self {
+        Self {
+            line: call.line(),
+            origin: call.origin(),
+            destination: &call.destinationDisplay.frontText,
+            quay: &call.quay,
+            aimed_arrival_time: call.aimedArrivalTime.as_deref(),
+            expected_arrival_time: call.expectedArrivalTime.as_deref(),
+            minutes_until_arrival: call.minutes_until_arrival(&Local::now()),
+            delay_minutes: call.arrival_delay_minutes(),
+            realtime: call.realtime,
+            cancelled: call.cancellation,
+            prediction_inaccurate: call.predictionInaccurate,
+            occupancy_status: call.occupancyStatus,
+            service_journey_id: call.serviceJourney.as_ref().map(|sj| sj.id.as_str()),
+            situations: &call.situations,
+        }
+    }
+}
+
+/// A service journey as written by `journey --format json`. The vehicle
+/// position is worked out against the local clock when the output is written,
+/// and is `null` when the journey has no realtime data.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JourneyRecord<'a> {
+    pub id: &'a str,
+    pub line: &'a Line,
+    pub destination: Option<&'a str>,
+    pub vehicle_position: Option<VehiclePositionRecord>,
+    pub calls: Vec<JourneyCallRecord<'a>>,
+}
+
+impl<'a> From<&'a Journey> for JourneyRecord<'a> {
+    fn from(journey: &'a Journey) -> Self {
+        let position = journey.vehicle_position(&Local::now());
+
+        Self {
+            id: &journey.id,
+            line: &journey.line,
+            destination: journey
+                .estimatedCalls
+                .first()
+                .and_then(|call| call.destinationDisplay.as_ref())
+                .map(|display| display.frontText.as_str()),
+            vehicle_position: position.map(VehiclePositionRecord::from),
+            calls: journey
+                .estimatedCalls
+                .iter()
+                .enumerate()
+                .map(|(index, call)| JourneyCallRecord::new(call, index, position))
+                .collect(),
+        }
+    }
+}
+
+/// Where the vehicle is: `notStarted`, `atStop`, `departed` or `finished`.
+/// `callIndex` is the call it is at or last left.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VehiclePositionRecord {
+    pub status: &'static str,
+    pub call_index: Option<usize>,
+}
+
+impl From<VehiclePosition> for VehiclePositionRecord {
+    fn from(position: VehiclePosition) -> Self {
+        let (status, call_index) = match position {
+            VehiclePosition::NotStarted => ("notStarted", None),
+            VehiclePosition::AtStop(index) => ("atStop", Some(index)),
+            VehiclePosition::Departed(index) => ("departed", Some(index)),
+            VehiclePosition::Finished => ("finished", None),
+        };
+
+        Self { status, call_index }
+    }
+}
+
+/// One stop of a service journey, also written per line by
+/// `journey --format ndjson`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JourneyCallRecord<'a> {
+    pub index: usize,
+    pub quay: &'a Quay,
+    pub aimed_arrival_time: &'a str,
+    pub expected_arrival_time: &'a str,
+    pub actual_arrival_time: Option<&'a str>,
+    pub aimed_departure_time: &'a str,
+    pub expected_departure_time: &'a str,
+    pub actual_departure_time: Option<&'a str>,
+    pub realtime: bool,
+    pub cancelled: bool,
+    /// The vehicle has left this stop behind.
+    pub passed: bool,
+    /// The vehicle is standing at this stop.
+    pub vehicle_at_stop: bool,
+}
+
+impl<'a> JourneyCallRecord<'a> {
+    fn new(call: &'a JourneyCall, index: usize, position: Option<VehiclePosition>) -> Self {
+        Self {
+            index,
+            quay: &call.quay,
+            aimed_arrival_time: &call.aimedArrivalTime,
+            expected_arrival_time: &call.expectedArrivalTime,
+            actual_arrival_time: call.actualArrivalTime.as_deref(),
+            aimed_departure_time: &call.aimedDepartureTime,
+            expected_departure_time: &call.expectedDepartureTime,
+            actual_departure_time: call.actualDepartureTime.as_deref(),
+            realtime: call.realtime,
+            cancelled: call.cancellation,
+            passed: position.is_some_and(|position| position.has_passed(index)),
+            vehicle_at_stop: position == Some(VehiclePosition::AtStop(index)),
+        }
+    }
+}
+
+/// A trip pattern; `duration` is in seconds and `walkDistance` in meters.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TripPatternRecord<'a> {
+    pub duration: i64,
+    pub walk_distance: f64,
+    pub legs: Vec<LegRecord<'a>>,
+}
+
+impl<'a> From<&'a TripPattern> for TripPatternRecord<'a> {
+    fn from(pattern: &'a TripPattern) -> Self {
+        Self {
+            duration: pattern.duration,
+            walk_distance: pattern.walkDistance,
+            legs: pattern.legs.iter().map(LegRecord::from).collect(),
+        }
+    }
+}
+
+/// One leg of a trip pattern. Walking legs have no line, quays or departures.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LegRecord<'a> {
+    pub mode: &'a Mode,
+    pub line: Option<&'a Line>,
+    pub destination: Option<&'a str>,
+    pub duration: i64,
+    pub distance: f64,
+    pub expected_start_time: &'a str,
+    pub expected_end_time: &'a str,
+    pub from: Option<DepartureRecord<'a>>,
+    pub to: Option<DepartureRecord<'a>>,
+    pub situations: &'a [Situation],
+}
+
+impl<'a> From<&'a Leg> for LegRecord<'a> {
+    fn from(leg: &'a Leg) -> Self {
+        Self {
+            mode: &leg.mode,
+            line: leg.line.as_ref(),
+            destination: leg
+                .fromEstimatedCall
+                .as_ref()
+                .map(|call| call.destinationDisplay.frontText.as_str()),
+            duration: leg.duration,
+            distance: leg.distance,
+            expected_start_time: &leg.expectedStartTime,
+            expected_end_time: &leg.expectedEndTime,
+            from: leg.fromEstimatedCall.as_ref().map(DepartureRecord::from),
+            to: leg.toEstimatedCall.as_ref().map(DepartureRecord::from),
+            situations: &leg.situations,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StopPlaceDepartures<'a> {
+    id: &'a str,
+    name: &'a str,
+    departures: Vec<DepartureRecord<'a>>,
+}
+
+/// A section of a grouped departure board.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DepartureGroupRecord<'a> {
+    pub title: &'a str,
+    pub departures: Vec<DepartureRecord<'a>>,
+}
+
+impl<'a> From<&'a DepartureGroup<'a>> for DepartureGroupRecord<'a> {
+    fn from(group: &'a DepartureGroup<'a>) -> Self {
+        Self {
+            title: &group.title,
+            departures: group
+                .departures
+                .iter()
+                .map(|call| DepartureRecord::from(*call))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StopPlaceGroups<'a> {
+    id: &'a str,
+    name: &'a str,
+    groups: Vec<DepartureGroupRecord<'a>>,
+}
+
+/// A line as written by `line --format json`. `journeyPatterns` is empty
+/// when the line was not looked up on its own.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LineRecord<'a> {
+    pub id: &'a str,
+    pub public_code: Option<&'a str>,
+    pub name: Option<&'a str>,
+    pub transport_mode: Option<&'a str>,
+    pub authority: Option<&'a Organisation>,
+    pub operator: Option<&'a Organisation>,
+    pub journey_patterns: Vec<JourneyPatternRecord<'a>>,
+}
+
+impl<'a> From<&'a LineInfo> for LineRecord<'a> {
+    fn from(line: &'a LineInfo) -> Self {
+        Self {
+            id: &line.id,
+            public_code: line.publicCode.as_deref(),
+            name: line.name.as_deref(),
+            transport_mode: line.transportMode.as_deref(),
+            authority: line.authority.as_ref(),
+            operator: line.operator.as_ref(),
+            journey_patterns: line
+                .journeyPatterns
+                .iter()
+                .map(JourneyPatternRecord::from)
+                .collect(),
+        }
+    }
+}
+
+/// One route of a line, also written per line by `line --format ndjson`.
+/// `quays` are in calling order.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JourneyPatternRecord<'a> {
+    pub id: &'a str,
+    pub name: Option<&'a str>,
+    pub direction_type: Option<&'a str>,
+    pub quays: &'a [Quay],
+}
+
+impl<'a> From<&'a JourneyPatternInfo> for JourneyPatternRecord<'a> {
+    fn from(pattern: &'a JourneyPatternInfo) -> Self {
+        Self {
+            id: &pattern.id,
+            name: pattern.name.as_deref(),
+            direction_type: pattern.directionType.as_deref(),
+            quays: &pattern.quays,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineDocument<'a> {
+    line: LineRecord<'a>,
+    stop_place: Option<StopPlaceDepartures<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StopPlaceArrivals<'a> {
+    id: &'a str,
+    name: &'a str,
+    arrivals: Vec<ArrivalRecord<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TripPatterns<'a> {
+    trip_patterns: Vec<TripPatternRecord<'a>>,
+}
+
+/// A stop place close to the requested coordinate; `distance` is in meters.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NearbyStopPlaceRecord<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub distance: f64,
+}
+
+impl<'a> From<&'a NearbyStopPlace> for NearbyStopPlaceRecord<'a> {
+    fn from(nearby: &'a NearbyStopPlace) -> Self {
+        Self {
+            id: &nearby.place.id,
+            name: &nearby.place.name,
+            distance: nearby.distance,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Nearby<'a> {
+    stop_places: Vec<NearbyStopPlaceRecord<'a>>,
+    departures: Vec<DepartureRecord<'a>>,
+}
+
+/// A vehicle as written by `vehicles --format json` and `--format ndjson`.
+///
+/// `delay` is in seconds behind the timetable, negative when early.
+/// `secondsSinceUpdate` is computed against the local clock when the output
+/// is written.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleRecord<'a> {
+    pub vehicle_id: Option<&'a str>,
+    pub line: Option<VehicleLineRecord<'a>>,
+    pub service_journey_id: Option<&'a str>,
+    pub destination: Option<&'a str>,
+    pub mode: Option<&'a str>,
+    pub last_updated: &'a str,
+    pub seconds_since_update: Option<i64>,
+    pub delay: Option<f64>,
+    pub delay_minutes: Option<i64>,
+    pub location: Option<&'a VehicleLocation>,
+    pub bearing: Option<f64>,
+    pub next_stop: Option<NextStopRecord<'a>>,
+}
+
+impl<'a> From<&'a Vehicle> for VehicleRecord<'a> {
+    fn from(vehicle: &'a Vehicle) -> Self {
+        Self {
+            vehicle_id: vehicle.vehicleId.as_deref(),
+            line: vehicle.line.as_ref().map(|line| VehicleLineRecord {
+                id: &line.lineRef,
+                public_code: line.publicCode.as_deref(),
+                name: line.lineName.as_deref(),
+            }),
+            service_journey_id: vehicle.serviceJourney.as_ref().map(|sj| sj.id.as_str()),
+            destination: vehicle.destinationName.as_deref(),
+            mode: vehicle.mode.as_deref(),
+            last_updated: &vehicle.lastUpdated,
+            seconds_since_update: vehicle.seconds_since_update(&Local::now()),
+            delay: vehicle.delay,
+            delay_minutes: vehicle.delay_minutes(),
+            location: vehicle.location.as_ref(),
+            bearing: vehicle.bearing,
+            next_stop: NextStopRecord::new(vehicle),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleLineRecord<'a> {
+    pub id: &'a str,
+    pub public_code: Option<&'a str>,
+    pub name: Option<&'a str>,
+}
+
+/// The quay a vehicle is at or heading for. `name` and `publicCode` are
+/// `null` when the quay could not be looked up.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NextStopRecord<'a> {
+    pub id: &'a str,
+    pub name: Option<&'a str>,
+    pub public_code: Option<&'a str>,
+    pub vehicle_at_stop: bool,
+}
+
+impl<'a> NextStopRecord<'a> {
+    fn new(vehicle: &'a Vehicle) -> Option<Self> {
+        let call = vehicle.monitoredCall.as_ref()?;
+        let quay = vehicle.nextQuay.as_ref();
+
+        Some(Self {
+            id: quay
+                .map(|quay| quay.id.as_str())
+                .or(call.stopPointRef.as_deref())?,
+            name: quay.map(|quay| quay.name.as_str()),
+            public_code: quay.and_then(|quay| quay.publicCode.as_deref()),
+            vehicle_at_stop: call.vehicleAtStop,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Vehicles<'a> {
+    vehicles: Vec<VehicleRecord<'a>>,
+}
+
+/// A stop place from the stop register as written by `stop info --format json`.
+///
+/// Accessibility values are `TRUE`, `FALSE`, `PARTIAL` or `UNKNOWN`, as in
+/// the stop register.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StopInfoRecord<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub stop_place_type: Option<&'a str>,
+    pub transport_mode: Option<&'a str>,
+    pub location: Option<LocationRecord>,
+    pub accessibility: Option<&'a Limitations>,
+    pub quays: Vec<QuayInfoRecord<'a>>,
+    pub parent: Option<RelatedStopPlaceRecord<'a>>,
+    pub adjacent: Vec<RelatedStopPlaceRecord<'a>>,
+    /// Stop places grouped under a multimodal parent.
+    pub children: Vec<RelatedStopPlaceRecord<'a>>,
+}
+
+impl<'a> From<&'a StopInfo> for StopInfoRecord<'a> {
+    fn from(info: &'a StopInfo) -> Self {
+        let stop_place = &info.stopPlace;
+
+        Self {
+            id: &stop_place.id,
+            name: stop_place.name(),
+            stop_place_type: stop_place.stopPlaceType.as_deref(),
+            transport_mode: stop_place.transportMode.as_deref(),
+            location: LocationRecord::new(&stop_place.geometry),
+            accessibility: limitations(&stop_place.accessibilityAssessment),
+            quays: stop_place.quays.iter().map(QuayInfoRecord::from).collect(),
+            parent: info.parent.as_ref().map(RelatedStopPlaceRecord::from),
+            adjacent: info
+                .adjacent
+                .iter()
+                .map(RelatedStopPlaceRecord::from)
+                .collect(),
+            children: stop_place
+                .children
+                .iter()
+                .map(RelatedStopPlaceRecord::from)
+                .collect(),
+        }
+    }
+}
+
+/// A quay of a stop place, also written per line by `stop info --format
+/// ndjson`. `compassBearing` is in degrees.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QuayInfoRecord<'a> {
+    pub id: &'a str,
+    pub public_code: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub compass_bearing: Option<f64>,
+    pub location: Option<LocationRecord>,
+    pub accessibility: Option<&'a Limitations>,
+}
+
+impl<'a> From<&'a QuayInfo> for QuayInfoRecord<'a> {
+    fn from(quay: &'a QuayInfo) -> Self {
+        Self {
+            id: &quay.id,
+            public_code: quay.publicCode.as_deref(),
+            description: quay.description.as_ref().map(|text| text.value.as_str()),
+            compass_bearing: quay.compassBearing,
+            location: LocationRecord::new(&quay.geometry),
+            accessibility: limitations(&quay.accessibilityAssessment),
+        }
+    }
+}
+
+/// A parent, adjacent or child stop place.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedStopPlaceRecord<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub stop_place_type: Option<&'a str>,
+    pub transport_mode: Option<&'a str>,
+}
+
+impl<'a> From<&'a StopPlaceInfo> for RelatedStopPlaceRecord<'a> {
+    fn from(stop_place: &'a StopPlaceInfo) -> Self {
+        Self {
+            id: &stop_place.id,
+            name: stop_place.name(),
+            stop_place_type: stop_place.stopPlaceType.as_deref(),
+            transport_mode: stop_place.transportMode.as_deref(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct LocationRecord {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl LocationRecord {
+    fn new(geometry: &Option<GeoJson>) -> Option<Self> {
+        let (latitude, longitude) = geometry.as_ref()?.lat_lon()?;
+
+        Some(Self {
+            latitude,
+            longitude,
+        })
+    }
+}
+
+fn limitations(assessment: &Option<AccessibilityAssessment>) -> Option<&Limitations> {
+    assessment.as_ref()?.limitations()
+}
+
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn departures(&self, out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()> {
+        let document = StopPlaceDepartures {
+            id: &stop_place.id,
+            name: &stop_place.name,
+            departures: stop_place
+                .estimatedCalls
+                .iter()
+                .map(DepartureRecord::from)
+                .collect(),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &document)?;
+        writeln!(out)
+    }
+
+    fn arrivals(&self, out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()> {
+        let document = StopPlaceArrivals {
+            id: &stop_place.id,
+            name: &stop_place.name,
+            arrivals: stop_place
+                .estimatedCalls
+                .iter()
+                .map(ArrivalRecord::from)
+                .collect(),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &document)?;
+        writeln!(out)
+    }
+
+    fn departure_groups(
+        &self,
+        out: &mut dyn Write,
+        stop_place: &StopPlace,
+        groups: &[DepartureGroup],
+    ) -> io::Result<()> {
+        let document = StopPlaceGroups {
+            id: &stop_place.id,
+            name: &stop_place.name,
+            groups: groups.iter().map(DepartureGroupRecord::from).collect(),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &document)?;
+        writeln!(out)
+    }
+
+    fn line(
+        &self,
+        out: &mut dyn Write,
+        line: &LineInfo,
+        stop_place: Option<&StopPlace>,
+    ) -> io::Result<()> {
+        let document = LineDocument {
+            line: LineRecord::from(line),
+            stop_place: stop_place.map(|stop_place| StopPlaceDepartures {
+                id: &stop_place.id,
+                name: &stop_place.name,
+                departures: stop_place
+                    .estimatedCalls
+                    .iter()
+                    .map(DepartureRecord::from)
+                    .collect(),
+            }),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &document)?;
+        writeln!(out)
+    }
+
+    fn trip_patterns(&self, out: &mut dyn Write, patterns: &[TripPattern]) -> io::Result<()> {
+        let document = TripPatterns {
+            trip_patterns: patterns.iter().map(TripPatternRecord::from).collect(),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &document)?;
+        writeln!(out)
+    }
+
+    fn journey(&self, out: &mut dyn Write, journey: &Journey) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &JourneyRecord::from(journey))?;
+        writeln!(out)
+    }
+
+    fn vehicles(&self, out: &mut dyn Write, vehicles: &[Vehicle]) -> io::Result<()> {
+        let vehicles = vehicles.iter().map(VehicleRecord::from).collect();
+        serde_json::to_writer_pretty(&mut *out, &Vehicles { vehicles })?;
+        writeln!(out)
+    }
+
+    fn stop_info(&self, out: &mut dyn Write, info: &StopInfo) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &StopInfoRecord::from(info))?;
+        writeln!(out)
+    }
+
+    fn nearby(
+        &self,
+        out: &mut dyn Write,
+        stop_places: &[NearbyStopPlace],
+        departures: &[EstimatedCall],
+    ) -> io::Result<()> {
+        let document = Nearby {
+            stop_places: stop_places
+                .iter()
+                .map(NearbyStopPlaceRecord::from)
+                .collect(),
+            departures: departures.iter().map(DepartureRecord::from).collect(),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &document)?;
+        writeln!(out)
+    }
+}
+
+pub struct NdjsonRenderer;
+
+impl Renderer for NdjsonRenderer {
+    fn departures(&self, out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()> {
+        for call in &stop_place.estimatedCalls {
+            serde_json::to_writer(&mut *out, &DepartureRecord::from(call))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    fn arrivals(&self, out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()> {
+        for call in &stop_place.estimatedCalls {
+            serde_json::to_writer(&mut *out, &ArrivalRecord::from(call))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// One line per group.
+    fn departure_groups(
+        &self,
+        out: &mut dyn Write,
+        _stop_place: &StopPlace,
+        groups: &[DepartureGroup],
+    ) -> io::Result<()> {
+        for group in groups {
+            serde_json::to_writer(&mut *out, &DepartureGroupRecord::from(group))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// One line per departure when a stop was chosen, otherwise one line per
+    /// journey pattern.
+    fn line(
+        &self,
+        out: &mut dyn Write,
+        line: &LineInfo,
+        stop_place: Option<&StopPlace>,
+    ) -> io::Result<()> {
+        match stop_place {
+            Some(stop_place) => {
+                for call in &stop_place.estimatedCalls {
+                    serde_json::to_writer(&mut *out, &DepartureRecord::from(call))?;
+                    writeln!(out)?;
+                }
+            }
+            None => {
+                for pattern in &line.journeyPatterns {
+                    serde_json::to_writer(&mut *out, &JourneyPatternRecord::from(pattern))?;
+                    writeln!(out)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn trip_patterns(&self, out: &mut dyn Write, patterns: &[TripPattern]) -> io::Result<()> {
+        for pattern in patterns {
+            serde_json::to_writer(&mut *out, &TripPatternRecord::from(pattern))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// One line per call.
+    fn journey(&self, out: &mut dyn Write, journey: &Journey) -> io::Result<()> {
+        for call in JourneyRecord::from(journey).calls {
+            serde_json::to_writer(&mut *out, &call)?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    fn vehicles(&self, out: &mut dyn Write, vehicles: &[Vehicle]) -> io::Result<()> {
+        for vehicle in vehicles {
+            serde_json::to_writer(&mut *out, &VehicleRecord::from(vehicle))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// One line per quay.
+    fn stop_info(&self, out: &mut dyn Write, info: &StopInfo) -> io::Result<()> {
+        for quay in &info.stopPlace.quays {
+            serde_json::to_writer(&mut *out, &QuayInfoRecord::from(quay))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// One line per departure, like `departures`.
+    fn nearby(
+        &self,
+        out: &mut dyn Write,
+        _stop_places: &[NearbyStopPlace],
+        departures: &[EstimatedCall],
+    ) -> io::Result<()> {
+        for call in departures {
+            serde_json::to_writer(&mut *out, &DepartureRecord::from(call))?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,955 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+
+use super::Renderer;
+use crate::departure::DepartureGroup;
+use crate::model::{
+    AccessibilityAssessment, EstimatedCall, Feature, GeoJson, Journey, LineInfo, Mode,
+    NearbyStopPlace, OccupancyStatus, Situation, StopInfo, StopPlace, TripPattern, ValidityPeriod,
+    Vehicle, VehiclePosition,
+};
+
+/// Lists `items` numbered from 1, as the prompt expects them.
+pub fn print_choices<T>(
+    out: &mut impl Write,
+    items: &[T],
+    label: impl Fn(&T) -> String,
+) -> io::Result<()> {
+    for (i, item) in items.iter().enumerate() {
+        writeln!(out, "\x1b[32m{}\x1b[0m - {}", i + 1, label(item))?;
+    }
+
+    Ok(())
+}
+
+/// A geocoder match as listed by [`print_choices`].
+pub fn feature_choice(feature: &Feature) -> String {
+    let kind = if feature.properties.is_address() {
+        "Address"
+    } else {
+        "Stop"
+    };
+
+    format!(
+        "\x1b[1m{}\x1b[0m ({} - {}) \x1b[2m· {}\x1b[0m",
+        feature.properties.name, feature.properties.locality, feature.properties.county, kind
+    )
+}
+
+/// A line as listed by [`print_choices`].
+pub fn line_choice(line: &LineInfo) -> String {
+    let mut label = format!("\x1b[1m{}\x1b[0m {}", line.public_code(), line.name());
+
+    if let Some(authority) = &line.authority {
+        label.push_str(&format!(" ({})", authority.name));
+    }
+
+    if let Some(mode) = &line.transportMode {
+        label.push_str(&format!(" \x1b[2m· {}\x1b[0m", mode));
+    }
+
+    label
+}
+
+/// Departures within the next ten minutes are shown as a countdown, others as
+/// a clock time. Departures on another day than `now` also get the date, so a
+/// board for `--at` or one that runs past midnight reads right.
+pub fn format_departure_time<Tz: TimeZone>(
+    expected_departure: &DateTime<FixedOffset>,
+    now: &DateTime<Tz>,
+) -> String {
+    let arrives_in_minutes: i64 = expected_departure.signed_duration_since(now).num_minutes();
+    let expected_departure_formatted = clock_time(expected_departure, now);
+
+    if (0..=10).contains(&arrives_in_minutes) {
+        format!(
+            "\x1b[1m{:?} min\x1b[0m ({})",
+            arrives_in_minutes, expected_departure_formatted
+        )
+    } else {
+        format!("\x1b[1m{}\x1b[0m", expected_departure_formatted)
+    }
+}
+
+/// `HH:MM`, with the date in front when `time` is on another day than `now`.
+fn clock_time<Tz: TimeZone>(time: &DateTime<FixedOffset>, now: &DateTime<Tz>) -> String {
+    let today = now.with_timezone(&time.timezone()).date_naive();
+
+    if time.date_naive() == today {
+        time.format("%H:%M").to_string()
+    } else {
+        time.format("%a %d.%m %H:%M").to_string()
+    }
+}
+
+/// The departure time of a call together with whether it is realtime (●) or
+/// scheduled (○), its delay and any notes. Cancelled calls show the aimed time
+/// struck through.
+pub fn format_call_time<Tz: TimeZone>(
+    call: &EstimatedCall,
+    expected_departure: &DateTime<FixedOffset>,
+    now: &DateTime<Tz>,
+) -> String {
+    format_time(
+        call,
+        call.aimed_departure(),
+        expected_departure,
+        call.delay_minutes(),
+        now,
+    )
+}
+
+/// Like [`format_call_time`], for the arrival time of a call.
+pub fn format_arrival_time<Tz: TimeZone>(
+    call: &EstimatedCall,
+    expected_arrival: &DateTime<FixedOffset>,
+    now: &DateTime<Tz>,
+) -> String {
+    format_time(
+        call,
+        call.aimed_arrival(),
+        expected_arrival,
+        call.arrival_delay_minutes(),
+        now,
+    )
+}
+
+fn format_time<Tz: TimeZone>(
+    call: &EstimatedCall,
+    aimed: Option<DateTime<FixedOffset>>,
+    expected: &DateTime<FixedOffset>,
+    delay: Option<i64>,
+    now: &DateTime<Tz>,
+) -> String {
+    if call.cancellation {
+        let aimed = aimed.unwrap_or(*expected);
+
+        return format!(
+            "\x1b[9m{}\x1b[0m \x1b[31;1mCancelled\x1b[0m",
+            clock_time(&aimed, now)
+        );
+    }
+
+    let mut text = if call.realtime {
+        String::from("\x1b[32m●\x1b[0m ")
+    } else {
+        String::from("\x1b[2m○\x1b[0m ")
+    };
+
+    text.push_str(&format_departure_time(expected, now));
+
+    match delay {
+        Some(delay) if call.realtime && delay > 0 => {
+            text.push_str(&format!(" \x1b[31m+{}\x1b[0m", delay));
+        }
+        Some(delay) if call.realtime && delay < 0 => {
+            text.push_str(&format!(" \x1b[32m{}\x1b[0m", delay));
+        }
+        _ => {}
+    }
+
+    if call.predictionInaccurate {
+        text.push_str(" \x1b[2m(uncertain)\x1b[0m");
+    }
+
+    match call.occupancyStatus {
+        Some(OccupancyStatus::noData) | None => {}
+        Some(occupancy) => text.push_str(&format!(" \x1b[2m· {}\x1b[0m", occupancy)),
+    }
+
+    text
+}
+
+/// "18.10 08:00 – 20.10 23:59", or an open-ended "from"/"until" period.
+pub fn format_validity_period(period: &ValidityPeriod) -> Option<String> {
+    let format = "%d.%m %H:%M";
+
+    match (period.start(), period.end()) {
+        (Some(start), Some(end)) => {
+            Some(format!("{} – {}", start.format(format), end.format(format)))
+        }
+        (Some(start), None) => Some(format!("from {}", start.format(format))),
+        (None, Some(end)) => Some(format!("until {}", end.format(format))),
+        (None, None) => None,
+    }
+}
+
+/// Prints service alerts as warnings. An alert in `seen` has already been
+/// printed in full further up and is only referred to by its summary.
+pub fn print_situations(
+    out: &mut impl Write,
+    situations: &[Situation],
+    seen: &mut HashSet<String>,
+    indent: &str,
+) -> io::Result<()> {
+    for situation in situations {
+        let summary = situation
+            .summary_text()
+            .or_else(|| situation.description_text())
+            .unwrap_or("Service alert");
+
+        if !seen.insert(situation.id.clone()) {
+            writeln!(
+                out,
+                "{}\x1b[33m⚠\x1b[0m {} \x1b[2m(see above)\x1b[0m",
+                indent, summary
+            )?;
+            continue;
+        }
+
+        let color = match situation.severity {
+            Some(severity) if severity.is_severe() => "31",
+            _ => "33",
+        };
+
+        write!(out, "{}\x1b[{}m⚠\x1b[0m ", indent, color)?;
+        if let Some(severity) = situation.severity {
+            write!(out, "\x1b[{};1m{}:\x1b[0m ", color, severity)?;
+        }
+        writeln!(out, "\x1b[1m{}\x1b[0m", summary)?;
+
+        for text in [situation.description_text(), situation.advice_text()]
+            .into_iter()
+            .flatten()
+            .filter(|text| *text != summary)
+        {
+            writeln!(out, "{}  {}", indent, text)?;
+        }
+
+        if let Some(period) = situation
+            .validityPeriod
+            .as_ref()
+            .and_then(format_validity_period)
+        {
+            writeln!(out, "{}  \x1b[2m{}\x1b[0m", indent, period)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Departures with their line, destination and time. When the departures
+/// come from more than one stop, each is labelled with its stop.
+pub fn print_departures(out: &mut impl Write, departures: &[EstimatedCall]) -> io::Result<()> {
+    let departures: Vec<&EstimatedCall> = departures.iter().collect();
+
+    print_departure_list(out, &departures, &mut HashSet::new())
+}
+
+/// Each group as a heading followed by its departures. Service alerts are
+/// printed in full once across all groups.
+pub fn print_departure_groups(out: &mut impl Write, groups: &[DepartureGroup]) -> io::Result<()> {
+    let mut seen = HashSet::new();
+
+    for group in groups {
+        writeln!(out, "\x1b[1;4m{}\x1b[0m", group.title)?;
+        writeln!(out)?;
+        print_departure_list(out, &group.departures, &mut seen)?;
+    }
+
+    Ok(())
+}
+
+fn print_departure_list(
+    out: &mut impl Write,
+    departures: &[&EstimatedCall],
+    seen: &mut HashSet<String>,
+) -> io::Result<()> {
+    let several_stops = departures
+        .iter()
+        .any(|call| call.quay.name != departures[0].quay.name);
+
+    for call in departures.iter().copied() {
+        let Some(expected_departure) = call.expected_departure() else {
+            continue;
+        };
+
+        let now = chrono::offset::Local::now();
+
+        if let Some(line) = call.line() {
+            write!(out, "\x1b[97;42;1m {} \x1b[0m", line.publicCode)?;
+        }
+
+        if call.cancellation {
+            write!(out, " \x1b[9m{}\x1b[0m", call.destinationDisplay.frontText)?;
+        } else {
+            write!(out, " {}", call.destinationDisplay.frontText)?;
+        }
+
+        if several_stops {
+            write!(out, " \x1b[2mfrom {}\x1b[0m", call.quay.name)?;
+        }
+        writeln!(out)?;
+
+        writeln!(
+            out,
+            " {}",
+            format_call_time(call, &expected_departure, &now)
+        )?;
+        print_situations(out, &call.situations, seen, " ")?;
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Arrivals with the line, where the vehicle is coming from and the arrival
+/// time.
+pub fn print_arrivals(out: &mut impl Write, arrivals: &[EstimatedCall]) -> io::Result<()> {
+    let mut seen = HashSet::new();
+    let several_stops = arrivals
+        .iter()
+        .any(|call| call.quay.name != arrivals[0].quay.name);
+
+    for call in arrivals {
+        let Some(expected_arrival) = call.expected_arrival() else {
+            continue;
+        };
+
+        let now = chrono::offset::Local::now();
+
+        if let Some(line) = call.line() {
+            write!(out, "\x1b[97;42;1m {} \x1b[0m", line.publicCode)?;
+        }
+
+        match call.origin() {
+            Some(origin) if call.cancellation => {
+                write!(out, " from \x1b[9m{}\x1b[0m", origin.name)?
+            }
+            Some(origin) => write!(out, " from {}", origin.name)?,
+            None => write!(out, " \x1b[2morigin unknown\x1b[0m")?,
+        }
+
+        if several_stops {
+            write!(out, " \x1b[2mat {}\x1b[0m", call.quay.name)?;
+        }
+        writeln!(out)?;
+
+        writeln!(
+            out,
+            " {}",
+            format_arrival_time(call, &expected_arrival, &now)
+        )?;
+        print_situations(out, &call.situations, &mut seen, " ")?;
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// A line with its authority and operator and the quays of each journey
+/// pattern. When `stop_place` is given, its departures on the line follow as
+/// a timetable.
+pub fn print_line(
+    out: &mut impl Write,
+    line: &LineInfo,
+    stop_place: Option<&StopPlace>,
+) -> io::Result<()> {
+    write!(
+        out,
+        "\x1b[97;42;1m {} \x1b[0m \x1b[1m{}\x1b[0m",
+        line.public_code(),
+        line.name()
+    )?;
+    if let Some(mode) = &line.transportMode {
+        write!(out, " \x1b[2m· {}\x1b[0m", mode)?;
+    }
+    writeln!(out)?;
+
+    let mut organisations = Vec::new();
+    if let Some(authority) = &line.authority {
+        organisations.push(format!("Authority: {}", authority.name));
+    }
+    if let Some(operator) = &line.operator {
+        organisations.push(format!("Operator: {}", operator.name));
+    }
+    if !organisations.is_empty() {
+        writeln!(out, "{}", organisations.join(" · "))?;
+    }
+
+    for pattern in &line.journeyPatterns {
+        writeln!(out)?;
+
+        let title = match (pattern.quays.first(), pattern.quays.last()) {
+            (Some(first), Some(last)) => format!("{} – {}", first.name, last.name),
+            _ => pattern.name.clone().unwrap_or_else(|| pattern.id.clone()),
+        };
+        write!(out, "\x1b[1m{}\x1b[0m", title)?;
+        if let Some(direction) = &pattern.directionType {
+            write!(out, " \x1b[2m· {}\x1b[0m", direction)?;
+        }
+        writeln!(out)?;
+
+        for (i, quay) in pattern.quays.iter().enumerate() {
+            writeln!(out, "{:>4}  {}", i + 1, quay.name)?;
+        }
+    }
+
+    if let Some(stop_place) = stop_place {
+        writeln!(out)?;
+        writeln!(out, "\x1b[1;4mDepartures from {}\x1b[0m", stop_place.name)?;
+        print_timetable(out, &stop_place.estimatedCalls)?;
+    }
+
+    Ok(())
+}
+
+/// Scheduled departures per destination, one row of minutes per hour like a
+/// printed timetable. Cancelled departures are struck through.
+fn print_timetable(out: &mut impl Write, departures: &[EstimatedCall]) -> io::Result<()> {
+    if departures.is_empty() {
+        writeln!(out)?;
+        return writeln!(out, "No departures");
+    }
+
+    let mut destinations: Vec<&str> = Vec::new();
+    for call in departures {
+        if !destinations.contains(&call.destinationDisplay.frontText.as_str()) {
+            destinations.push(&call.destinationDisplay.frontText);
+        }
+    }
+
+    for destination in destinations {
+        writeln!(out)?;
+        writeln!(out, "\x1b[1m{}\x1b[0m", destination)?;
+
+        let mut hour: Option<String> = None;
+
+        for call in departures
+            .iter()
+            .filter(|call| call.destinationDisplay.frontText == destination)
+        {
+            let Some(aimed) = call.aimed_departure() else {
+                continue;
+            };
+
+            let call_hour = aimed.format("%H").to_string();
+            if hour.as_ref() != Some(&call_hour) {
+                if hour.is_some() {
+                    writeln!(out)?;
+                }
+                write!(out, "  {} │", call_hour)?;
+                hour = Some(call_hour);
+            }
+
+            if call.cancellation {
+                write!(out, " \x1b[9m{}\x1b[0m", aimed.format("%M"))?;
+            } else {
+                write!(out, " {}", aimed.format("%M"))?;
+            }
+        }
+
+        if hour.is_some() {
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_nearby_stop_places(
+    out: &mut impl Write,
+    stop_places: &[NearbyStopPlace],
+) -> io::Result<()> {
+    for nearby in stop_places {
+        writeln!(
+            out,
+            "\x1b[1m{:>6.0} m\x1b[0m  {}",
+            nearby.distance, nearby.place.name
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Every stop of a service journey with aimed, expected and actual
+/// departure times. Stops already passed are dimmed and the vehicle is marked
+/// with ▶ when its position is known.
+pub fn print_journey<Tz: TimeZone>(
+    out: &mut impl Write,
+    journey: &Journey,
+    now: &DateTime<Tz>,
+) -> io::Result<()> {
+    let destination = journey
+        .estimatedCalls
+        .first()
+        .and_then(|call| call.destinationDisplay.as_ref())
+        .map(|display| display.frontText.as_str())
+        .unwrap_or(&journey.line.name);
+
+    writeln!(
+        out,
+        "\x1b[97;42;1m {} \x1b[0m \x1b[1m{}\x1b[0m",
+        journey.line.publicCode, destination
+    )?;
+    writeln!(out)?;
+    writeln!(out, "  \x1b[2mAimed  Expected  Actual\x1b[0m")?;
+
+    let position = journey.vehicle_position(now);
+    let time = |time: Option<DateTime<FixedOffset>>| {
+        time.map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_default()
+    };
+
+    for (i, call) in journey.estimatedCalls.iter().enumerate() {
+        let passed = position.is_some_and(|position| position.has_passed(i));
+
+        let marker = if position == Some(VehiclePosition::AtStop(i)) {
+            "\x1b[32;1m▶\x1b[0m "
+        } else {
+            "  "
+        };
+
+        let row = format!(
+            "{:<5}  {:<8}  {:<6}  {}",
+            time(call.aimed_departure()),
+            time(call.expected_departure()),
+            time(call.actual_departure()),
+            call.quay.name
+        );
+
+        write!(out, "{}", marker)?;
+        if call.cancellation {
+            write!(out, "\x1b[9m{}\x1b[0m \x1b[31;1mCancelled\x1b[0m", row)?;
+        } else if passed {
+            write!(out, "\x1b[2m{}\x1b[0m", row)?;
+        } else {
+            write!(out, "{}", row)?;
+        }
+
+        if let Some(public_code) = &call.quay.publicCode {
+            write!(out, " \x1b[1mSpor {}\x1b[0m", public_code)?;
+        }
+        writeln!(out)?;
+
+        if position == Some(VehiclePosition::Departed(i)) {
+            writeln!(out, "\x1b[32;1m▶\x1b[0m \x1b[32m···\x1b[0m")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Where each vehicle is heading, how late it is and how long ago it last
+/// reported its position. Positions older than five minutes are highlighted.
+pub fn print_vehicles<Tz: TimeZone>(
+    out: &mut impl Write,
+    vehicles: &[Vehicle],
+    now: &DateTime<Tz>,
+) -> io::Result<()> {
+    for (i, vehicle) in vehicles.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+
+        let public_code = vehicle
+            .line
+            .as_ref()
+            .and_then(|line| line.publicCode.as_deref())
+            .unwrap_or("?");
+        writeln!(
+            out,
+            "\x1b[97;42;1m {} \x1b[0m \x1b[1m{}\x1b[0m",
+            public_code,
+            vehicle.destinationName.as_deref().unwrap_or_default()
+        )?;
+
+        if let Some(stop) = vehicle.next_stop() {
+            let at_stop = vehicle
+                .monitoredCall
+                .as_ref()
+                .is_some_and(|call| call.vehicleAtStop);
+            let label = if at_stop { "At stop" } else { "Next stop" };
+
+            write!(out, "     {}: {}", label, stop)?;
+            if let Some(public_code) = vehicle
+                .nextQuay
+                .as_ref()
+                .and_then(|quay| quay.publicCode.as_deref())
+            {
+                write!(out, " \x1b[2m({})\x1b[0m", public_code)?;
+            }
+            writeln!(out)?;
+        }
+
+        let mut status = Vec::new();
+        match vehicle.delay_minutes() {
+            Some(delay) if delay > 0 => status.push(format!("\x1b[31m+{} min\x1b[0m", delay)),
+            Some(delay) if delay < 0 => status.push(format!("\x1b[32m{} min\x1b[0m", delay)),
+            Some(_) => status.push(String::from("on time")),
+            None => {}
+        }
+        if let Some(age) = vehicle.seconds_since_update(now) {
+            let updated = format!("updated {} ago", format_age(age));
+
+            if age > 5 * 60 {
+                status.push(format!("\x1b[33m{}\x1b[0m", updated));
+            } else {
+                status.push(updated);
+            }
+        }
+        if !status.is_empty() {
+            writeln!(out, "     {}", status.join(" · "))?;
+        }
+
+        let mut ids = Vec::new();
+        if let Some(service_journey) = &vehicle.serviceJourney {
+            ids.push(service_journey.id.clone());
+        }
+        if let Some(vehicle_id) = &vehicle.vehicleId {
+            ids.push(format!("vehicle {}", vehicle_id));
+        }
+        if !ids.is_empty() {
+            writeln!(out, "     \x1b[2m{}\x1b[0m", ids.join(" · "))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// "45s", "3 min" or "2 h".
+fn format_age(seconds: i64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 60 * 60 {
+        format!("{} min", seconds / 60)
+    } else {
+        format!("{} h", seconds / (60 * 60))
+    }
+}
+
+/// A stop place from the stop register: its quays, accessibility and the
+/// stop places around it.
+pub fn print_stop_info(out: &mut impl Write, info: &StopInfo) -> io::Result<()> {
+    let stop_place = &info.stopPlace;
+
+    writeln!(
+        out,
+        "\x1b[1m{}\x1b[0m \x1b[2m{}\x1b[0m",
+        stop_place.name(),
+        stop_place.id
+    )?;
+
+    let kind: Vec<&str> = [&stop_place.stopPlaceType, &stop_place.transportMode]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    if !kind.is_empty() {
+        writeln!(out, "Type: {}", kind.join(", "))?;
+    }
+
+    if let Some((latitude, longitude)) = stop_place.geometry.as_ref().and_then(GeoJson::lat_lon) {
+        writeln!(out, "Location: {:.5}, {:.5}", latitude, longitude)?;
+    }
+
+    if let Some(accessibility) = format_accessibility(&stop_place.accessibilityAssessment) {
+        writeln!(out, "Accessibility: {}", accessibility)?;
+    }
+
+    if !stop_place.quays.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "\x1b[1mQuays\x1b[0m")?;
+    }
+
+    for quay in &stop_place.quays {
+        write!(
+            out,
+            "\x1b[97;42;1m {:>4} \x1b[0m {}",
+            quay.publicCode.as_deref().unwrap_or(""),
+            quay.id
+        )?;
+
+        if let Some(description) = &quay.description {
+            write!(out, " {}", description.value)?;
+        }
+
+        writeln!(out)?;
+
+        let mut details = Vec::new();
+        if let Some(bearing) = quay.compassBearing {
+            details.push(format!("bearing {:.0}°", bearing));
+        }
+        if let Some((latitude, longitude)) = quay.geometry.as_ref().and_then(GeoJson::lat_lon) {
+            details.push(format!("{:.5}, {:.5}", latitude, longitude));
+        }
+        if let Some(accessibility) = format_accessibility(&quay.accessibilityAssessment) {
+            details.push(accessibility);
+        }
+
+        if !details.is_empty() {
+            writeln!(out, "       \x1b[2m{}\x1b[0m", details.join(" · "))?;
+        }
+    }
+
+    let related = [
+        ("Part of", info.parent.as_slice()),
+        ("Adjacent stop places", info.adjacent.as_slice()),
+        ("Stop places", stop_place.children.as_slice()),
+    ];
+
+    for (heading, stop_places) in related {
+        if stop_places.is_empty() {
+            continue;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "\x1b[1m{}\x1b[0m", heading)?;
+
+        for related in stop_places {
+            writeln!(out, "  {} \x1b[2m{}\x1b[0m", related.name(), related.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// "wheelchair: yes, step free: partial", leaving out unknown values.
+fn format_accessibility(assessment: &Option<AccessibilityAssessment>) -> Option<String> {
+    let limitations = assessment.as_ref()?.limitations()?;
+
+    let values = [
+        ("wheelchair", &limitations.wheelchairAccess),
+        ("step free", &limitations.stepFreeAccess),
+        ("escalator free", &limitations.escalatorFreeAccess),
+        ("lift free", &limitations.liftFreeAccess),
+        ("audible signals", &limitations.audibleSignalsAvailable),
+        ("visual signs", &limitations.visualSignsAvailable),
+    ];
+
+    let known: Vec<String> = values
+        .into_iter()
+        .filter_map(|(name, value)| match value.as_deref()? {
+            "TRUE" => Some(format!("{}: yes", name)),
+            "FALSE" => Some(format!("{}: no", name)),
+            "PARTIAL" => Some(format!("{}: partial", name)),
+            _ => None,
+        })
+        .collect();
+
+    if known.is_empty() {
+        None
+    } else {
+        Some(known.join(", "))
+    }
+}
+
+/// One line per departure, as used by the live board in `departure --watch`.
+/// Departures in `changed` have their time highlighted and departures that
+/// have already left are skipped. Each line clears the rest of the row so the
+/// board can be redrawn in place.
+pub fn print_board<Tz: TimeZone>(
+    out: &mut impl Write,
+    departures: &[EstimatedCall],
+    changed: &HashSet<String>,
+    now: &DateTime<Tz>,
+) -> io::Result<()> {
+    for call in departures {
+        let Some(expected_departure) = call.expected_departure() else {
+            continue;
+        };
+
+        if expected_departure.signed_duration_since(now).num_seconds() < 0 {
+            continue;
+        }
+
+        let line = call
+            .line()
+            .map(|line| line.publicCode.as_str())
+            .unwrap_or("");
+        write!(out, "\x1b[97;42;1m {:>4} \x1b[0m ", line)?;
+
+        if call.cancellation {
+            write!(
+                out,
+                "\x1b[9m{:<32}\x1b[0m ",
+                call.destinationDisplay.frontText
+            )?;
+        } else {
+            write!(out, "{:<32} ", call.destinationDisplay.frontText)?;
+        }
+
+        write!(out, "{}", format_call_time(call, &expected_departure, now))?;
+
+        if !call.situations.is_empty() {
+            write!(out, " \x1b[33m⚠\x1b[0m")?;
+        }
+
+        if changed.contains(&call.key()) {
+            write!(out, " \x1b[33;1m(updated)\x1b[0m")?;
+        }
+
+        writeln!(out, "\x1b[K")?;
+    }
+
+    Ok(())
+}
+
+pub fn print_trip_patterns(out: &mut impl Write, patterns: &[TripPattern]) -> io::Result<()> {
+    let mut seen = HashSet::new();
+
+    for pattern in patterns {
+        let duration = chrono::Duration::seconds(pattern.duration);
+        let hours = duration.num_hours();
+        let minutes = duration.num_minutes() - (hours * 60);
+
+        write!(out, "Travel time:")?;
+        if hours > 0 {
+            write!(out, " {} t", hours)?;
+        }
+        writeln!(out, " {} min", minutes)?;
+        writeln!(out)?;
+
+        for (i, leg) in pattern.legs.iter().enumerate() {
+            // println!("Mode: {}", leg.mode);
+
+            if let (0, None, Some(from_place)) = (i, &leg.fromEstimatedCall, &leg.fromPlace) {
+                if let Ok(expected_start) = DateTime::parse_from_rfc3339(&leg.expectedStartTime) {
+                    write!(out, "\x1b[1m{}\x1b[0m • ", expected_start.format("%H:%M"))?;
+                }
+
+                writeln!(out, "{}", from_place)?;
+            }
+
+            if let Some(from_estimated_call) = &leg.fromEstimatedCall {
+                if let Ok(expected_departure) =
+                    DateTime::parse_from_rfc3339(&from_estimated_call.aimedDepartureTime)
+                {
+                    write!(
+                        out,
+                        "\x1b[1m{}\x1b[0m • ",
+                        expected_departure.format("%H:%M")
+                    )?;
+                }
+
+                write!(out, "{}", from_estimated_call.quay.name)?;
+
+                if let Some(public_code) = &from_estimated_call.quay.publicCode {
+                    write!(out, " \x1b[1mSpor {}\x1b[0m ", public_code)?;
+                }
+
+                writeln!(out)?;
+            }
+
+            if leg.mode == Mode::foot {
+                writeln!(out, "      . ")?;
+                writeln!(
+                    out,
+                    "      . Walk {} minutes",
+                    chrono::Duration::seconds(leg.duration).num_minutes()
+                )?;
+                writeln!(out, "      . ")?;
+            } else {
+                writeln!(out, "      |")?;
+                if let Some(line) = &leg.line {
+                    write!(out, "      | \x1b[97;42m {} \x1b[0m ", line.publicCode)?;
+                }
+
+                if let Some(to_estimated_call) = &leg.toEstimatedCall {
+                    writeln!(out, "{}", to_estimated_call.destinationDisplay.frontText)?;
+                }
+
+                writeln!(
+                    out,
+                    "      | {} min",
+                    chrono::Duration::seconds(leg.duration).num_minutes()
+                )?;
+                print_situations(out, &leg.situations, &mut seen, "      | ")?;
+                writeln!(out, "      |")?;
+                writeln!(out, "      |")?;
+            }
+
+            if let Some(to_estimated_call) = &leg.toEstimatedCall {
+                if let Ok(expected_departure) =
+                    DateTime::parse_from_rfc3339(&to_estimated_call.aimedDepartureTime)
+                {
+                    write!(
+                        out,
+                        "\x1b[1m{}\x1b[0m • ",
+                        expected_departure.format("%H:%M")
+                    )?;
+                }
+
+                write!(out, "{} ", to_estimated_call.quay.name)?;
+
+                if let Some(public_code) = &to_estimated_call.quay.publicCode {
+                    write!(out, " \x1b[1mSpor {}\x1b[0m ", public_code)?;
+                }
+
+                writeln!(out)?;
+            }
+            writeln!(out)?;
+        }
+
+        writeln!(
+            out,
+            "================================================================================"
+        )?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+pub struct TextRenderer;
+
+impl Renderer for TextRenderer {
+    fn departures(&self, mut out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()> {
+        print_departures(&mut out, &stop_place.estimatedCalls)
+    }
+
+    fn arrivals(&self, mut out: &mut dyn Write, stop_place: &StopPlace) -> io::Result<()> {
+        print_arrivals(&mut out, &stop_place.estimatedCalls)
+    }
+
+    fn departure_groups(
+        &self,
+        mut out: &mut dyn Write,
+        _stop_place: &StopPlace,
+        groups: &[DepartureGroup],
+    ) -> io::Result<()> {
+        print_departure_groups(&mut out, groups)
+    }
+
+    fn line(
+        &self,
+        mut out: &mut dyn Write,
+        line: &LineInfo,
+        stop_place: Option<&StopPlace>,
+    ) -> io::Result<()> {
+        print_line(&mut out, line, stop_place)
+    }
+
+    fn trip_patterns(&self, mut out: &mut dyn Write, patterns: &[TripPattern]) -> io::Result<()> {
+        print_trip_patterns(&mut out, patterns)
+    }
+
+    fn journey(&self, mut out: &mut dyn Write, journey: &Journey) -> io::Result<()> {
+        print_journey(&mut out, journey, &chrono::Local::now())
+    }
+
+    fn vehicles(&self, mut out: &mut dyn Write, vehicles: &[Vehicle]) -> io::Result<()> {
+        print_vehicles(&mut out, vehicles, &chrono::Local::now())
+    }
+
+    fn stop_info(&self, mut out: &mut dyn Write, info: &StopInfo) -> io::Result<()> {
+        print_stop_info(&mut out, info)
+    }
+
+    fn nearby(
+        &self,
+        mut out: &mut dyn Write,
+        stop_places: &[NearbyStopPlace],
+        departures: &[EstimatedCall],
+    ) -> io::Result<()> {
+        print_nearby_stop_places(&mut out, stop_places)?;
+        writeln!(out)?;
+        writeln!(out, "----------------------------------")?;
+        writeln!(out)?;
+        print_departures(&mut out, departures)
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+/// Parses durations such as `30s`, `5m`, `2h` or `1h30m`. A bare number is
+/// taken as seconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let invalid = || format!("invalid duration '{}', expected e.g. 30s, 5m or 2h", value);
+
+    let total = match value.parse::<u64>() {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            let mut total: u64 = 0;
+            let mut number = String::new();
+
+            for c in value.chars() {
+                if c.is_ascii_digit() {
+                    number.push(c);
+                    continue;
+                }
+
+                let unit: u64 = match c {
+                    's' => 1,
+                    'm' => 60,
+                    'h' => 60 * 60,
+                    'd' => 24 * 60 * 60,
+                    _ => return Err(invalid()),
+                };
+
+                total = number
+                    .parse::<u64>()
+                    .ok()
+                    .and_then(|amount| amount.checked_mul(unit))
+                    .and_then(|seconds| total.checked_add(seconds))
+                    .ok_or_else(invalid)?;
+                number.clear();
+            }
+
+            if !number.is_empty() {
+                return Err(invalid());
+            }
+
+            total
+        }
+    };
+
+    if total == 0 {
+        return Err(invalid());
+    }
+
+    Ok(Duration::from_secs(total))
+}
+
+/// Parses `today`, `tomorrow` or an ISO 8601 date such as `2026-12-24`.
+pub fn parse_date(value: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let value = value.trim();
+
+    match value.to_lowercase().as_str() {
+        "today" => Ok(today),
+        "tomorrow" => Ok(today + chrono::Days::new(1)),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+            format!(
+                "invalid date '{}', expected e.g. today, tomorrow or 2026-12-24",
+                value
+            )
+        }),
+    }
+}
+
+/// Parses a point in time relative to `now`: a clock time (`08:15`), a day
+/// followed by a clock time (`tomorrow 07:30`, `2026-12-24 18:00`) or an ISO
+/// 8601 timestamp with or without an offset. Times without an offset are in
+/// the time zone of `now`.
+pub fn parse_date_time<Tz: TimeZone>(
+    value: &str,
+    now: &DateTime<Tz>,
+) -> Result<DateTime<FixedOffset>, String> {
+    let value = value.trim();
+    let invalid = || {
+        format!(
+            "invalid time '{}', expected e.g. 08:15, tomorrow 07:30 or 2026-12-24T18:00",
+            value
+        )
+    };
+
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time);
+    }
+
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(value, format) {
+            return local(now, date_time).ok_or_else(invalid);
+        }
+    }
+
+    let (date, time) = match value.rsplit_once(' ') {
+        Some((day, time)) => (parse_date(day, now.date_naive())?, time),
+        None => (now.date_naive(), value),
+    };
+
+    let time = NaiveTime::parse_from_str(time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+        .map_err(|_| invalid())?;
+
+    local(now, date.and_time(time)).ok_or_else(invalid)
+}
+
+fn local<Tz: TimeZone>(
+    now: &DateTime<Tz>,
+    date_time: NaiveDateTime,
+) -> Option<DateTime<FixedOffset>> {
+    now.timezone()
+        .from_local_datetime(&date_time)
+        .earliest()
+        .map(|date_time| date_time.fixed_offset())
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::model::Mode;
+
+/// Options for planning a trip. The default is to leave now with any mode of
+/// transport.
+///
+/// Everything except `max_walk_distance` is sent to Entur as part of the
+/// query. Trip patterns that walk further are dropped afterwards, and later
+/// pages are fetched until `count` patterns are left.
+#[derive(Debug, Default, Clone)]
+pub struct TripOptions {
+    /// When to leave, or when to arrive if `arrive_by` is set.
+    pub date_time: Option<DateTime<FixedOffset>>,
+    pub arrive_by: bool,
+    /// Only travel with these modes. Empty means all.
+    pub modes: Vec<Mode>,
+    pub exclude_modes: Vec<Mode>,
+    pub max_transfers: Option<u32>,
+    /// Walking speed in meters per second.
+    pub walk_speed: Option<f64>,
+    /// Longest total walk in meters.
+    pub max_walk_distance: Option<f64>,
+    pub wheelchair: bool,
+    pub prefer_fewer_transfers: bool,
+    /// How many trip patterns to ask for. Entur decides when unset.
+    pub count: Option<u32>,
+    /// A `nextPageCursor` or `previousPageCursor` from an earlier response.
+    pub page_cursor: Option<String>,
+}
+
+impl TripOptions {
+    /// The same search, continued from a page cursor.
+    pub fn page(&self, cursor: &str) -> Self {
+        Self {
+            page_cursor: Some(cursor.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// The transit modes to white-list in the query, or `None` when every
+    /// mode is allowed.
+    pub fn transport_modes(&self) -> Option<Vec<Mode>> {
+        if self.modes.is_empty() && self.exclude_modes.is_empty() {
+            return None;
+        }
+
+        let modes: &[Mode] = if self.modes.is_empty() {
+            &Mode::ALL
+        } else {
+            &self.modes
+        };
+
+        Some(
+            modes
+                .iter()
+                .copied()
+                .filter(|mode| mode.is_transit() && !self.exclude_modes.contains(mode))
+                .collect(),
+        )
+    }
+}
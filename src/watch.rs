@@ -0,0 +1,149 @@
+//! Live departure board for `departure --watch`.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+use crate::EnTurClient;
+use crate::departure::DepartureOptions;
+use crate::error::BusterminalError;
+use crate::filter::DepartureFilter;
+use crate::model::StopPlace;
+use crate::render;
+
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+
+/// Redraws the departures from `stop_place` in an alternate screen buffer,
+/// counting down every second and fetching new data every `interval`, until
+/// Ctrl-C is pressed. Each refresh starts from the current time.
+pub async fn watch_departures(
+    client: &EnTurClient,
+    stop_place: StopPlace,
+    options: &DepartureOptions,
+    filter: &DepartureFilter,
+    interval: Duration,
+    out: &mut impl Write,
+) -> Result<(), BusterminalError> {
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    write!(out, "{}", ENTER_ALTERNATE_SCREEN)?;
+
+    let mut board = Board {
+        stop_place,
+        changed: HashSet::new(),
+        updated: Local::now(),
+        error: None,
+    };
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(1));
+    let mut refreshed_at = Instant::now();
+
+    let result = loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break Ok(()),
+            _ = ticker.tick() => {}
+        }
+
+        if refreshed_at.elapsed() >= interval {
+            let options = DepartureOptions {
+                start_time: None,
+                ..options.clone()
+            };
+            let refresh = client.get_stop_place(&board.stop_place.id, &options, filter);
+
+            tokio::select! {
+                _ = &mut ctrl_c => break Ok(()),
+                next = refresh => board.update(next),
+            }
+
+            refreshed_at = Instant::now();
+        }
+
+        let next_refresh = interval.saturating_sub(refreshed_at.elapsed());
+        if let Err(error) = board.draw(out, next_refresh) {
+            break Err(error.into());
+        }
+    };
+
+    write!(out, "{}", LEAVE_ALTERNATE_SCREEN)?;
+    out.flush()?;
+
+    result
+}
+
+struct Board {
+    stop_place: StopPlace,
+    changed: HashSet<String>,
+    updated: DateTime<Local>,
+    error: Option<String>,
+}
+
+impl Board {
+    /// Keeps showing the previous departures when a refresh fails, so a
+    /// network hiccup does not blank the board.
+    fn update(&mut self, next: Result<StopPlace, BusterminalError>) {
+        match next {
+            Ok(next) => {
+                self.changed = changed_calls(&self.stop_place, &next);
+                self.stop_place = next;
+                self.updated = Local::now();
+                self.error = None;
+            }
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write, next_refresh: Duration) -> std::io::Result<()> {
+        write!(out, "\x1b[H")?;
+        writeln!(
+            out,
+            "\x1b[1mDepartures for \x1b[4m{}\x1b[0m\x1b[K",
+            self.stop_place.name
+        )?;
+        writeln!(
+            out,
+            "Updated {} · next refresh in {}s · Ctrl-C to quit\x1b[K",
+            self.updated.format("%H:%M:%S"),
+            next_refresh.as_secs()
+        )?;
+        writeln!(out, "\x1b[K")?;
+
+        render::print_board(
+            out,
+            &self.stop_place.estimatedCalls,
+            &self.changed,
+            &Local::now(),
+        )?;
+
+        if let Some(error) = &self.error {
+            writeln!(out, "\x1b[K")?;
+            writeln!(out, "\x1b[31mX\x1b[0m Refresh failed: {}\x1b[K", error)?;
+        }
+
+        write!(out, "\x1b[J")?;
+        out.flush()
+    }
+}
+
+/// Keys of calls whose expected departure differs from the previous fetch.
+fn changed_calls(previous: &StopPlace, next: &StopPlace) -> HashSet<String> {
+    let previous: HashMap<String, &str> = previous
+        .estimatedCalls
+        .iter()
+        .map(|call| (call.key(), call.expectedDepartureTime.as_str()))
+        .collect();
+
+    next.estimatedCalls
+        .iter()
+        .filter(|call| {
+            previous
+                .get(&call.key())
+                .is_some_and(|expected| *expected != call.expectedDepartureTime)
+        })
+        .map(|call| call.key())
+        .collect()
+}
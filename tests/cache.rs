@@ -0,0 +1,95 @@
+mod common;
+
+use std::fs;
+use std::time::Duration;
+
+use busterminal::cache::Cache;
+use common::TempDir;
+
+#[test]
+fn stored_values_are_returned() {
+    let dir = TempDir::new("stored");
+    let cache = Cache::new(dir.path());
+
+    cache.put("geocoder", "venue:Tyholt", &vec!["NSR:StopPlace:42660"]);
+
+    assert_eq!(
+        cache.get::<Vec<String>>("geocoder", "venue:Tyholt"),
+        Some(vec![String::from("NSR:StopPlace:42660")])
+    );
+    assert_eq!(cache.get::<Vec<String>>("stops", "venue:Tyholt"), None);
+}
+
+#[test]
+fn keys_ignore_case_and_surrounding_whitespace() {
+    let dir = TempDir::new("keys");
+    let cache = Cache::new(dir.path());
+
+    cache.put("geocoder", "venue:Tyholt", &1);
+
+    assert_eq!(cache.get::<u32>("geocoder", " venue:tyholt "), Some(1));
+    assert_eq!(cache.get::<u32>("geocoder", "venue:Tyholttårnet"), None);
+}
+
+#[test]
+fn expired_entries_are_only_used_when_stale_is_asked_for() {
+    let dir = TempDir::new("expired");
+    let cache = Cache::new(dir.path()).ttl(Duration::ZERO);
+
+    cache.put("stops", "NSR:StopPlace:42660", &"Tyholt");
+
+    assert_eq!(cache.get::<String>("stops", "NSR:StopPlace:42660"), None);
+    assert_eq!(
+        cache.get_stale::<String>("stops", "NSR:StopPlace:42660"),
+        Some(String::from("Tyholt"))
+    );
+}
+
+#[test]
+fn put_drops_expired_entries() {
+    let dir = TempDir::new("prune");
+    fs::create_dir_all(dir.path()).unwrap();
+    fs::write(
+        dir.join("stops.json"),
+        r#"{"nsr:stopplace:58211":{"storedAt":0,"value":"Lade"}}"#,
+    )
+    .unwrap();
+
+    let cache = Cache::new(dir.path());
+    cache.put("stops", "NSR:StopPlace:42660", &"Tyholt");
+
+    assert_eq!(
+        cache.get_stale::<String>("stops", "NSR:StopPlace:58211"),
+        None
+    );
+    assert_eq!(
+        cache.get::<String>("stops", "NSR:StopPlace:42660"),
+        Some(String::from("Tyholt"))
+    );
+}
+
+#[test]
+fn refresh_ignores_stored_entries() {
+    let dir = TempDir::new("refresh");
+    Cache::new(dir.path()).put("stops", "NSR:StopPlace:42660", &"Tyholt");
+
+    let cache = Cache::new(dir.path()).refresh(true);
+
+    assert_eq!(cache.get::<String>("stops", "NSR:StopPlace:42660"), None);
+}
+
+#[test]
+fn unreadable_files_are_misses() {
+    let dir = TempDir::new("unreadable");
+    fs::create_dir_all(dir.path()).unwrap();
+    fs::write(dir.join("stops.json"), "not json").unwrap();
+
+    let cache = Cache::new(dir.path());
+    assert_eq!(cache.get::<String>("stops", "NSR:StopPlace:42660"), None);
+
+    cache.put("stops", "NSR:StopPlace:42660", &"Tyholt");
+    assert_eq!(
+        cache.get::<String>("stops", "NSR:StopPlace:42660"),
+        Some(String::from("Tyholt"))
+    );
+}
@@ -0,0 +1,1691 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::{MockEntur, Route, TempDir, fixture, strip_ansi};
+
+const JOURNEY_PLANNER: &str = "/journey-planner/v3/graphql";
+const GEOCODER: &str = "/geocoder/v1/autocomplete";
+const STOP_PLACES: &str = "/stop-places/v1/graphql";
+const VEHICLES: &str = "/realtime/v2/vehicles/graphql";
+
+/// Runs busterminal against `server` with `env` set on top of a config file
+/// and cache of its own, so runs never see each other's favourites or cached
+/// searches.
+fn busterminal_with_env(server: &MockEntur, env: &[(&str, &Path)], args: &[&str]) -> Output {
+    let home = TempDir::new("cli");
+
+    Command::new(env!("CARGO_BIN_EXE_busterminal"))
+        .env("BUSTERMINAL_BASE_URL", server.url())
+        .env("BUSTERMINAL_CLIENT_NAME", "busterminal-tests")
+        .env("BUSTERMINAL_CONFIG", home.join("config.toml"))
+        .env("BUSTERMINAL_CACHE_DIR", home.join("cache"))
+        .envs(env.iter().copied())
+        .args(args)
+        .output()
+        .expect("run busterminal")
+}
+
+fn busterminal(server: &MockEntur, args: &[&str]) -> Output {
+    busterminal_with_env(server, &[], args)
+}
+
+fn busterminal_with_config(server: &MockEntur, config: &Path, args: &[&str]) -> Output {
+    busterminal_with_env(server, &[("BUSTERMINAL_CONFIG", config)], args)
+}
+
+fn stdout(output: &Output) -> String {
+    strip_ansi(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn stderr(output: &Output) -> String {
+    strip_ansi(&String::from_utf8_lossy(&output.stderr))
+}
+
+#[test]
+fn departure_prints_board_for_stop() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+
+    let output = busterminal(&server, &["departure", "--stop", "Tyholt"]);
+    let stdout = stdout(&output);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout.starts_with("Searching for Tyholt\n"));
+    assert!(stdout.contains("Departures for Tyholt (Trondheim - Trøndelag)"));
+    assert!(stdout.contains(" 12  Lade via sentrum\n"));
+    assert!(stdout.contains(" 12  Dragvoll\n ○ Thu 01.01 12:34\n"));
+}
+
+#[test]
+fn departure_without_calls_exits_with_no_match() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_empty.json"),
+        ),
+    ]);
+
+    let output = busterminal(&server, &["departure", "--stop", "Tyholt"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(stderr(&output), "X No departures from Tyholt\n");
+}
+
+#[test]
+fn departure_exits_with_graphql_error() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(JOURNEY_PLANNER, "StopPlace", fixture("graphql_error.json")),
+    ]);
+
+    let output = busterminal(&server, &["departure", "--stop", "Tyholt"]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert!(stderr(&output).starts_with("X GraphQL error: "));
+}
+
+#[test]
+fn departure_as_json() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &["departure", "--stop", "Tyholt", "--format", "json"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["id"], "NSR:StopPlace:42660");
+    assert_eq!(document["name"], "Tyholt");
+
+    let first = &document["departures"][0];
+    assert_eq!(first["line"]["publicCode"], "12");
+    assert_eq!(first["destination"], "Lade via sentrum");
+    assert_eq!(first["quay"]["publicCode"], "1");
+    assert_eq!(first["minutesUntilDeparture"], 5);
+    assert_eq!(first["delayMinutes"], 2);
+    assert_eq!(first["realtime"], true);
+    assert_eq!(
+        first["serviceJourneyId"],
+        "ATB:ServiceJourney:12_230306097863631_113"
+    );
+}
+
+#[test]
+fn departure_as_ndjson() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &["departure", "--stop", "Tyholt", "--format", "ndjson"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["destination"], "Dragvoll");
+    assert_eq!(lines[1]["realtime"], false);
+    assert_eq!(lines[1]["delayMinutes"], 0);
+}
+
+#[test]
+fn timeout_of_zero_is_rejected() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--timeout",
+            "0",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("invalid duration '0'"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn ambiguous_stop_without_terminal_fails_instead_of_prompting() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_oslo.json"))]);
+
+    let output = busterminal(&server, &["departure", "--stop", "Oslo"]);
+    let stderr = stderr(&output);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("2 - Oslo bussterminal (Oslo - Oslo)"));
+    assert!(stderr.contains("'Oslo' matches 3 stops and stdin is not a terminal"));
+    assert!(!stdout(&output).contains("Which stop"));
+}
+
+#[test]
+fn departure_with_pick_chooses_without_prompting() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_oslo.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+
+    let output = busterminal(&server, &["departure", "--stop", "Oslo", "--pick", "2"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Departures for Oslo bussterminal (Oslo - Oslo)"));
+    assert_eq!(
+        server.requests()[1].json()["variables"]["id"],
+        "NSR:StopPlace:4000"
+    );
+}
+
+#[test]
+fn departure_with_pick_out_of_range_fails() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_oslo.json"))]);
+
+    let output = busterminal(&server, &["departure", "--stop", "Oslo", "--pick", "4"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Cannot pick stop 4"));
+}
+
+#[test]
+fn departure_with_stop_id_skips_geocoder() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_tyholt.json"),
+    )]);
+
+    let output = busterminal(&server, &["departure", "--stop-id", "NSR:StopPlace:42660"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Departures for Tyholt\n"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, JOURNEY_PLANNER);
+}
+
+#[test]
+fn trip_with_first_and_ids() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_oslo.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from",
+            "Oslo",
+            "--from-first",
+            "--to-id",
+            "NSR:StopPlace:58211",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let body = server.requests()[1].json();
+    assert_eq!(body["variables"]["from"]["place"], "NSR:StopPlace:59872");
+    assert_eq!(body["variables"]["to"]["place"], "NSR:StopPlace:58211");
+}
+
+#[test]
+fn trip_picks_each_end_separately() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_oslo.json")).containing("text=Oslo"),
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")).containing("text=Tyholt"),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from",
+            "Oslo",
+            "--from-pick",
+            "2",
+            "--to",
+            "Tyholt",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let body = server.requests()[2].json();
+    assert_eq!(body["variables"]["from"]["place"], "NSR:StopPlace:4000");
+    assert_eq!(body["variables"]["to"]["place"], "NSR:StopPlace:42660");
+}
+
+#[test]
+fn trip_names_the_flags_of_the_ambiguous_end() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")).containing("text=Tyholt"),
+        Route::get(GEOCODER, fixture("geocoder_oslo.json")).containing("text=Oslo"),
+    ]);
+
+    let output = busterminal(&server, &["trip", "--from", "Tyholt", "--to", "Oslo"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Choose one with --to-pick N or --to-first"));
+}
+
+#[test]
+fn trip_with_date_and_departure_time() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Trip",
+        fixture("trip_oslo_gardermoen.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from-id",
+            "NSR:StopPlace:59872",
+            "--to-id",
+            "NSR:StopPlace:58211",
+            "--date",
+            "2099-01-01",
+            "--depart-at",
+            "07:45",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Leaving at Thu 01.01 07:45"));
+
+    let body = server.requests()[0].json();
+    let date_time = body["variables"]["dateTime"].as_str().unwrap();
+    assert!(
+        date_time.starts_with("2099-01-01T07:45:00"),
+        "{}",
+        date_time
+    );
+    assert_eq!(body["variables"]["arriveBy"], false);
+}
+
+#[test]
+fn trip_with_invalid_time_fails() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from-id",
+            "NSR:StopPlace:59872",
+            "--to-id",
+            "NSR:StopPlace:58211",
+            "--arrive-by",
+            "soonish",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("invalid time 'soonish'"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn trip_from_coordinates_to_address() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_karl_johan.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_from_coordinates.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from",
+            "59.9111,10.7528",
+            "--to",
+            "Karl Johans gate 22",
+            "--to-pick",
+            "2",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].query.contains("layers=venue,address"));
+
+    let variables = &requests[1].json()["variables"];
+    assert_eq!(
+        variables["from"],
+        serde_json::json!({
+            "name": null,
+            "coordinates": { "latitude": 59.9111, "longitude": 10.7528 }
+        })
+    );
+    assert_eq!(variables["to"]["name"], "Karl Johans gate 22");
+    assert!(variables["to"]["coordinates"]["latitude"].as_f64().unwrap() > 59.91);
+    assert!(variables["to"].get("place").is_none());
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("08:00 • 59.9111,10.7528\n"), "{}", stdout);
+    assert!(stdout.contains("08:03 • Oslo S Spor 13"), "{}", stdout);
+}
+
+#[test]
+fn ambiguous_place_lists_addresses_and_stops() {
+    let server = MockEntur::start(vec![Route::get(
+        GEOCODER,
+        fixture("geocoder_karl_johan.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from",
+            "Karl Johan",
+            "--to-id",
+            "NSR:StopPlace:58211",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = stderr(&output);
+    assert!(stderr.contains("1 - Karl Johans gate (Oslo - Oslo) · Stop"));
+    assert!(stderr.contains("2 - Karl Johans gate 22 (Oslo - Oslo) · Address"));
+    assert!(stderr.contains("'Karl Johan' matches 2 places"));
+}
+
+#[test]
+fn trip_next_fetches_later_connections() {
+    let server = MockEntur::start(vec![
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen_later.json"),
+        )
+        .containing("MXxORVhUX1BBR0V8MjA5OS0wMS0wMVQwODoxMA=="),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from-id",
+            "NSR:StopPlace:59872",
+            "--to-id",
+            "NSR:StopPlace:58211",
+            "--count",
+            "3",
+            "--next",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("08:23 • Oslo S"));
+    assert!(!stdout(&output).contains("08:03 • Oslo S"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].json()["variables"]["numTripPatterns"], 3);
+    assert!(requests[0].json()["variables"].get("pageCursor").is_none());
+    assert_eq!(
+        requests[1].json()["variables"]["pageCursor"],
+        "MXxORVhUX1BBR0V8MjA5OS0wMS0wMVQwODoxMA=="
+    );
+    assert_eq!(requests[1].json()["variables"]["numTripPatterns"], 3);
+}
+
+#[test]
+fn trip_count_of_zero_is_rejected() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from-id",
+            "NSR:StopPlace:59872",
+            "--to-id",
+            "NSR:StopPlace:58211",
+            "--count",
+            "0",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--count"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn trip_walk_options_must_be_positive() {
+    let server = MockEntur::start(Vec::new());
+
+    for flag in ["--walk-speed", "--max-walk"] {
+        for value in ["0", "-5"] {
+            let output = busterminal(
+                &server,
+                &[
+                    "trip",
+                    "--from-id",
+                    "NSR:StopPlace:59872",
+                    "--to-id",
+                    "NSR:StopPlace:58211",
+                    flag,
+                    value,
+                ],
+            );
+
+            assert_eq!(output.status.code(), Some(2), "{} {}", flag, value);
+            assert!(stderr(&output).contains(&format!("invalid value '{}' for '{}", value, flag)));
+        }
+    }
+
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn trip_browse_needs_text_output_and_a_terminal() {
+    let server = MockEntur::start(Vec::new());
+    let trip = [
+        "trip",
+        "--from-id",
+        "NSR:StopPlace:59872",
+        "--to-id",
+        "NSR:StopPlace:58211",
+        "--browse",
+    ];
+
+    let output = busterminal(&server, &[&trip[..], &["--format", "json"]].concat());
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--browse can only be used with --format text"));
+
+    let output = busterminal(&server, &trip);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("stdin is not a terminal"));
+
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn trip_prints_patterns() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")).containing("text=Tyholt"),
+        Route::get(GEOCODER, fixture("geocoder_gardermoen.json")).containing("text=Gardermoen"),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+
+    let output = busterminal(&server, &["trip", "--from", "Tyholt", "--to", "Gardermoen"]);
+    let stdout = stdout(&output);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout.contains("Travel time: 25 min"));
+    assert!(stdout.contains("08:03 • Oslo S Spor 13"));
+
+    let body = server.requests()[2].json();
+    assert_eq!(body["variables"]["from"]["place"], "NSR:StopPlace:42660");
+    assert_eq!(body["variables"]["to"]["place"], "NSR:StopPlace:58211");
+}
+
+#[test]
+fn trip_with_unknown_stop_exits_with_no_match() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_empty.json"))]);
+
+    let output = busterminal(&server, &["trip", "--from", "Nowhere", "--to", "Oslo S"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(
+        stderr(&output),
+        "X Could not find any stops or addresses using query: Nowhere\n"
+    );
+}
+
+#[test]
+fn trip_as_json() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")).containing("text=Tyholt"),
+        Route::get(GEOCODER, fixture("geocoder_gardermoen.json")).containing("text=Gardermoen"),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "trip",
+            "--from",
+            "Tyholt",
+            "--to",
+            "Gardermoen",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let pattern = &document["tripPatterns"][0];
+    assert_eq!(pattern["duration"], 1500);
+
+    let legs = pattern["legs"].as_array().unwrap();
+    assert_eq!(legs[0]["mode"], "foot");
+    assert!(legs[0]["line"].is_null());
+    assert_eq!(legs[1]["mode"], "rail");
+    assert_eq!(legs[1]["line"]["publicCode"], "FX");
+    assert_eq!(legs[1]["from"]["quay"]["name"], "Oslo S");
+    assert_eq!(legs[1]["to"]["quay"]["name"], "Oslo lufthavn");
+}
+
+#[test]
+fn nearby_lists_stops_and_merged_departures() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Nearest",
+        fixture("nearest_oslo.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "nearby", "--lat", "59.9111", "--lon", "10.7528", "--radius", "300",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Stops within 300 m of 59.9111,10.7528"));
+    assert!(stdout.contains("    88 m  Oslo S\n   244 m  Jernbanetorget\n"));
+
+    let lillestrom = stdout.find(" L1  Lillestrøm from Oslo S").unwrap();
+    let ljabru = stdout.find(" 13  Ljabru from Jernbanetorget").unwrap();
+    let drammen = stdout.find(" R10  Drammen from Oslo S").unwrap();
+    assert!(lillestrom < ljabru && ljabru < drammen);
+
+    let variables = &server.requests()[0].json()["variables"];
+    assert_eq!(variables["latitude"], 59.9111);
+    assert_eq!(variables["longitude"], 10.7528);
+    assert_eq!(variables["maximumDistance"], 300.0);
+}
+
+#[test]
+fn nearby_as_json() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Nearest",
+        fixture("nearest_oslo.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "nearby", "--lat", "59.9111", "--lon", "10.7528", "--format", "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["stopPlaces"][0]["name"], "Oslo S");
+    assert_eq!(document["stopPlaces"][0]["distance"], 88.2);
+    assert_eq!(document["departures"].as_array().unwrap().len(), 3);
+    assert_eq!(document["departures"][1]["destination"], "Ljabru");
+}
+
+#[test]
+fn nearby_leaves_out_calls_without_boarding_and_sorts_unknown_times_last() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Nearest",
+        fixture("nearest_terminus.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "nearby", "--lat", "59.9111", "--lon", "10.7528", "--format", "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let departures = document["departures"].as_array().unwrap();
+    assert_eq!(departures.len(), 2);
+    assert_eq!(departures[0]["destination"], "Ljabru");
+    assert_eq!(departures[1]["destination"], "Kjelsås");
+}
+
+#[test]
+fn nearby_radius_must_be_positive() {
+    let server = MockEntur::start(Vec::new());
+
+    for radius in ["0", "-100", "far"] {
+        let output = busterminal(
+            &server,
+            &[
+                "nearby", "--lat", "59.9111", "--lon", "10.7528", "--radius", radius,
+            ],
+        );
+
+        assert_eq!(output.status.code(), Some(2), "{}", radius);
+        assert!(stderr(&output).contains(&format!("invalid value '{}' for '--radius", radius)));
+    }
+
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn nearby_without_stops_exits_with_no_match() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Nearest",
+        fixture("nearest_empty.json"),
+    )]);
+
+    let output = busterminal(&server, &["nearby", "--lat", "78.22", "--lon", "15.65"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(stderr(&output), "X No stops within 500 m of 78.22,15.65\n");
+}
+
+#[test]
+fn departure_follow_shows_the_journey() {
+    let server = MockEntur::start(vec![
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "ServiceJourney",
+            fixture("service_journey_12.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:42660",
+            "--follow",
+            "1",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("12:10  12:12             Tyholt Spor 1"));
+
+    let body = server.requests()[1].json();
+    assert_eq!(
+        body["variables"]["id"],
+        "ATB:ServiceJourney:12_230306097863631_113"
+    );
+    assert_eq!(body["variables"]["date"], "2099-01-01");
+}
+
+#[test]
+fn journey_as_ndjson() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "ServiceJourney",
+        fixture("service_journey_12.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "journey",
+            "ATB:ServiceJourney:12_230306097863631_113",
+            "--date",
+            "2099-01-01",
+            "--format",
+            "ndjson",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0]["quay"]["name"], "Dragvoll");
+    assert_eq!(lines[3]["actualDepartureTime"], serde_json::Value::Null);
+    assert_eq!(lines[1]["passed"], true);
+    assert_eq!(lines[2]["passed"], false);
+}
+
+#[test]
+fn journey_as_json_has_the_vehicle_position() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "ServiceJourney",
+        fixture("service_journey_12.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "journey",
+            "ATB:ServiceJourney:12_230306097863631_113",
+            "--date",
+            "2099-01-01",
+            "--format",
+            "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let journey: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(journey["id"], "ATB:ServiceJourney:12_230306097863631_113");
+    assert_eq!(journey["line"]["publicCode"], "12");
+    assert_eq!(journey["destination"], "Lade via sentrum");
+    assert_eq!(journey["vehiclePosition"]["status"], "departed");
+    assert_eq!(journey["vehiclePosition"]["callIndex"], 1);
+    assert_eq!(journey["calls"].as_array().unwrap().len(), 4);
+    assert_eq!(journey["calls"][1]["passed"], true);
+    assert_eq!(journey["calls"][1]["vehicleAtStop"], false);
+    assert_eq!(journey["calls"][2]["passed"], false);
+    assert_eq!(
+        journey["calls"][2]["aimedDepartureTime"],
+        "2099-01-01T12:10:00+01:00"
+    );
+}
+
+fn stop_register() -> MockEntur {
+    MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(STOP_PLACES, "StopInfo", fixture("stop_info_parent.json"))
+            .containing("NSR:StopPlace:60000"),
+        Route::graphql(STOP_PLACES, "StopInfo", fixture("stop_info_adjacent.json"))
+            .containing("NSR:StopPlace:42661"),
+        Route::graphql(STOP_PLACES, "StopInfo", fixture("stop_info_tyholt.json"))
+            .containing("NSR:StopPlace:42660"),
+    ])
+}
+
+#[test]
+fn stop_info_shows_quays_and_related_stops() {
+    let server = stop_register();
+
+    let output = busterminal(&server, &["stop", "info", "Tyholt"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let expected = "
+Tyholt NSR:StopPlace:42660
+Type: onstreetBus, bus
+Location: 63.42263, 10.43220
+Accessibility: wheelchair: yes, step free: partial, audible signals: no
+
+Quays
+    1  NSR:Quay:73976 mot sentrum
+       bearing 271° · 63.42270, 10.43201 · wheelchair: yes, step free: yes
+    2  NSR:Quay:73977
+
+Part of
+  Tyholt knutepunkt NSR:StopPlace:60000
+
+Adjacent stop places
+  Tyholttårnet NSR:StopPlace:42661
+";
+    assert_eq!(stdout(&output), expected);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(!requests[1].body.contains("onstreetBus"));
+}
+
+#[test]
+fn stop_info_by_id_as_json() {
+    let server = stop_register();
+
+    let output = busterminal(
+        &server,
+        &["stop", "info", "NSR:StopPlace:42660", "--format", "json"],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["id"], "NSR:StopPlace:42660");
+    assert_eq!(document["name"], "Tyholt");
+    assert_eq!(document["location"]["latitude"], 63.42263);
+    assert_eq!(document["accessibility"]["wheelchairAccess"], "TRUE");
+    assert_eq!(document["quays"][0]["compassBearing"], 271.0);
+    assert_eq!(document["quays"][0]["description"], "mot sentrum");
+    assert_eq!(document["quays"][1]["location"], serde_json::Value::Null);
+    assert_eq!(document["parent"]["id"], "NSR:StopPlace:60000");
+    assert_eq!(document["adjacent"][0]["id"], "NSR:StopPlace:42661");
+    assert!(
+        server
+            .requests()
+            .iter()
+            .all(|request| request.path == STOP_PLACES)
+    );
+}
+
+#[test]
+fn stop_info_for_unknown_id_exits_with_no_match() {
+    let server = MockEntur::start(vec![Route::graphql(
+        STOP_PLACES,
+        "StopInfo",
+        fixture("stop_info_empty.json"),
+    )]);
+
+    let output = busterminal(&server, &["stop", "info", "NSR:StopPlace:1"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(stderr(&output), "X Unknown stop place: NSR:StopPlace:1\n");
+}
+
+#[test]
+fn fav_add_saves_the_stop_and_departure_skips_the_search() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+    let dir = TempDir::new("fav-add");
+    let config = dir.join("config.toml");
+
+    let output = busterminal_with_config(&server, &config, &["fav", "add", "home", "Tyholt"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "Saved @home as Tyholt (Trondheim - Trøndelag)\n"
+    );
+    assert!(
+        fs::read_to_string(&config)
+            .unwrap()
+            .contains("NSR:StopPlace:42660")
+    );
+
+    let output = busterminal_with_config(&server, &config, &["departure", "--stop", "@home"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Departures for Tyholt"));
+    assert!(!stdout(&output).contains("Searching for"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].path, JOURNEY_PLANNER);
+    assert!(requests[1].body.contains("NSR:StopPlace:42660"));
+}
+
+#[test]
+fn trip_between_favourites_skips_the_search() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Trip",
+        fixture("trip_oslo_gardermoen.json"),
+    )]);
+    let dir = TempDir::new("fav-trip");
+    let config = dir.join("config.toml");
+    fs::create_dir_all(config.parent().unwrap()).unwrap();
+    fs::write(
+        &config,
+        r#"[favourites.home]
+id = "NSR:StopPlace:42660"
+name = "Tyholt"
+
+[favourites.work]
+id = "KVE:TopographicPlace:0301-Karl Johans gate 22"
+name = "Karl Johans gate 22"
+latitude = 59.91264
+longitude = 10.745121
+"#,
+    )
+    .unwrap();
+
+    let output = busterminal_with_config(
+        &server,
+        &config,
+        &["trip", "--from", "@home", "--to", "@work"],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+
+    let variables = &requests[0].json()["variables"];
+    assert_eq!(variables["from"]["place"], "NSR:StopPlace:42660");
+    assert_eq!(
+        variables["to"]["coordinates"]["latitude"],
+        f64::from(59.91264_f32)
+    );
+    assert_eq!(variables["to"]["name"], "Karl Johans gate 22");
+}
+
+#[test]
+fn unknown_favourite_exits_with_no_match() {
+    let server = MockEntur::start(vec![]);
+
+    let output = busterminal(&server, &["departure", "--stop", "@home"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(
+        stderr(&output),
+        "X Unknown favourite: @home. Save it with `busterminal fav add home`\n"
+    );
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn fav_list_and_remove() {
+    let server = MockEntur::start(vec![Route::get(
+        GEOCODER,
+        fixture("geocoder_karl_johan.json"),
+    )]);
+    let dir = TempDir::new("fav-list");
+    let config = dir.join("config.toml");
+
+    let output = busterminal_with_config(
+        &server,
+        &config,
+        &["fav", "add", "@work", "Karl Johans gate 22", "--pick", "2"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = busterminal_with_config(&server, &config, &["fav", "list"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "@work  Karl Johans gate 22  59.91264,10.745121\n"
+    );
+
+    let output = busterminal_with_config(&server, &config, &["fav", "remove", "work"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = busterminal_with_config(&server, &config, &["fav", "list"]);
+    assert!(stdout(&output).starts_with("No favourites yet"));
+}
+
+#[test]
+fn fav_list_aligns_non_ascii_aliases() {
+    let server = MockEntur::start(Vec::new());
+    let dir = TempDir::new("fav-align");
+    let config = dir.join("config.toml");
+    fs::create_dir_all(config.parent().unwrap()).unwrap();
+    fs::write(
+        &config,
+        r#"[favourites."skolen-på-ås"]
+id = "NSR:StopPlace:58211"
+name = "Ås skole"
+
+[favourites."værnes"]
+id = "NSR:StopPlace:58197"
+name = "Trondheim lufthavn"
+"#,
+    )
+    .unwrap();
+
+    let output = busterminal_with_config(&server, &config, &["fav", "list"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "@skolen-på-ås  Ås skole  NSR:StopPlace:58211
+@værnes        Trondheim lufthavn  NSR:StopPlace:58197
+"
+    );
+}
+
+#[test]
+fn repeated_searches_use_the_cache_until_refreshed() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+    let cache = TempDir::new("repeated-searches");
+    let run = |args: &[&str]| {
+        busterminal_with_env(&server, &[("BUSTERMINAL_CACHE_DIR", cache.path())], args)
+    };
+
+    for args in [
+        &["departure", "--stop", "Tyholt"][..],
+        &["departure", "--stop", "Tyholt"],
+        &["departure", "--stop", "Tyholt", "--refresh"],
+    ] {
+        let output = run(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+
+    let geocoder_requests = server
+        .requests()
+        .iter()
+        .filter(|request| request.path == GEOCODER)
+        .count();
+    assert_eq!(geocoder_requests, 2);
+}
+
+#[test]
+fn departure_at_a_past_time_shows_dates_instead_of_countdowns() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_tyholt_night.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:42660",
+            "--at",
+            "2020-03-02 23:30",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains(" ● Mon 02.03 23:57 +2\n"), "{}", stdout);
+    assert!(stdout.contains(" ○ Tue 03.03 00:25"), "{}", stdout);
+    assert!(!stdout.contains(" min"), "{}", stdout);
+}
+
+#[test]
+fn departure_at_a_later_time_sends_the_window() {
+    let server = MockEntur::start(vec![
+        Route::get(GEOCODER, fixture("geocoder_tyholt.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop",
+            "Tyholt",
+            "--at",
+            "2026-12-24 18:00",
+            "--window",
+            "2h",
+            "--limit",
+            "5",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(
+        stdout(&output)
+            .contains("Departures for Tyholt (Trondheim - Trøndelag)\nFrom Thu 24.12 18:00\n")
+    );
+
+    let requests = server.requests();
+    let variables = &requests[1].json()["variables"];
+    assert!(
+        variables["startTime"]
+            .as_str()
+            .unwrap()
+            .starts_with("2026-12-24T18:00:00")
+    );
+    assert_eq!(variables["timeRange"], 7200);
+    assert_eq!(variables["numberOfDepartures"], 5);
+}
+
+#[test]
+fn departure_with_invalid_time_is_rejected() {
+    let server = MockEntur::start(vec![]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:42660",
+            "--at",
+            "christmas",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("invalid time 'christmas'"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn departure_arrivals_as_json() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_arrivals.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:41742",
+            "--arrivals",
+            "--format",
+            "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let arrivals = document["arrivals"].as_array().unwrap();
+    assert_eq!(arrivals.len(), 2);
+    assert_eq!(arrivals[0]["origin"]["name"], "Værnes");
+    assert_eq!(arrivals[0]["delayMinutes"], 4);
+    assert!(arrivals[0]["minutesUntilArrival"].is_i64());
+    assert_eq!(arrivals[1]["aimedArrivalTime"], "2099-01-01T12:34:00+01:00");
+}
+
+#[test]
+fn departure_arrivals_heading() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_arrivals.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:41742",
+            "--arrivals",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Arrivals at Trondheim S\n"));
+    assert!(stdout(&output).contains(" R60  from Oppdal\n"));
+}
+
+#[test]
+fn departure_grouped_by_quay() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_oslo.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--group-by",
+            "quay",
+            "--per-group",
+            "1",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let text = stdout(&output);
+    let headings: Vec<&str> = text
+        .lines()
+        .filter(|line| line.starts_with("Spor") || line.starts_with("Platform"))
+        .collect();
+    assert_eq!(
+        headings,
+        ["Spor 2", "Spor 4", "Spor 5", "Platform A", "Platform B"]
+    );
+}
+
+#[test]
+fn departure_group_size_of_zero_is_rejected() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--group-by",
+            "quay",
+            "--per-group",
+            "0",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--per-group"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn departure_mode_must_be_public_transport() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--mode",
+            "bus,foot",
+        ],
+    );
+    let stderr = stderr(&output);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("'foot' is not a public transport mode"));
+    assert!(stderr.contains("bus, cableway, water"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn departure_limit_of_zero_is_rejected() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--limit",
+            "0",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--limit"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn departure_window_longer_than_a_graphql_int_is_rejected() {
+    let server = MockEntur::start(Vec::new());
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--window",
+            "100000d",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("invalid window '100000d'"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn departure_grouped_by_line_as_json() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_oslo.json"),
+    )]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "departure",
+            "--stop-id",
+            "NSR:StopPlace:59872",
+            "--group-by",
+            "line",
+            "--format",
+            "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let groups = document["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 4);
+    assert_eq!(
+        groups[0]["title"].as_str().unwrap().split(' ').next(),
+        Some("13")
+    );
+    let l1 = groups
+        .iter()
+        .find(|group| group["departures"].as_array().unwrap().len() == 2);
+    assert_eq!(l1.unwrap()["departures"][1]["destination"], "Spikkestad");
+}
+
+fn line_routes() -> Vec<Route> {
+    vec![
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_3.json"))
+            .containing("\"publicCode\":\"3\""),
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_all.json")),
+        Route::graphql(JOURNEY_PLANNER, "Line", fixture("line_12.json"))
+            .containing("ATB:Line:2_12"),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_line_12.json"),
+        )
+        .containing("ATB:Line:2_12"),
+    ]
+}
+
+#[test]
+fn line_shows_journey_patterns_and_timetable() {
+    let server = MockEntur::start(line_routes());
+
+    let output = busterminal(
+        &server,
+        &[
+            "line",
+            "12",
+            "--stop-id",
+            "NSR:StopPlace:42660",
+            "--date",
+            "2026-12-24",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let expected = "
+ 12  Dragvoll - Lade · bus
+Authority: AtB · Operator: Vy Buss
+
+Dragvoll – Lade · outbound
+   1  Dragvoll
+   2  Tyholt
+   3  Lade
+
+Lade – Dragvoll · inbound
+   1  Lade
+   2  Tyholt
+   3  Dragvoll
+
+Departures from Tyholt
+
+Lade via sentrum
+  08 │ 05 35
+  09 │ 05
+
+Dragvoll
+  08 │ 12
+  09 │ 42
+";
+    assert_eq!(stdout(&output), expected);
+
+    let stop_place = server
+        .requests()
+        .into_iter()
+        .find(|request| request.body.contains("query StopPlace("))
+        .unwrap();
+    assert!(
+        stop_place.json()["variables"]["startTime"]
+            .as_str()
+            .unwrap()
+            .starts_with("2026-12-24T00:00:00")
+    );
+}
+
+#[test]
+fn line_by_id_as_json_skips_search() {
+    let server = MockEntur::start(line_routes());
+
+    let output = busterminal(&server, &["line", "ATB:Line:2_12", "--format", "json"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["line"]["operator"]["name"], "Vy Buss");
+    assert_eq!(
+        document["line"]["journeyPatterns"][1]["directionType"],
+        "inbound"
+    );
+    assert!(document["stopPlace"].is_null());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn line_as_ndjson_writes_one_journey_pattern_per_line() {
+    let server = MockEntur::start(line_routes());
+
+    let output = busterminal(&server, &["line", "ATB:Line:2_12", "--format", "ndjson"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let patterns: Vec<serde_json::Value> = stdout(&output)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0]["id"], "ATB:JourneyPattern:2_12_1");
+    assert_eq!(patterns[0]["directionType"], "outbound");
+    assert_eq!(patterns[0]["quays"][0]["publicCode"], "1");
+}
+
+#[test]
+fn line_and_stop_are_picked_separately() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_3.json")),
+        Route::graphql(JOURNEY_PLANNER, "Line", fixture("line_12.json")).containing("RUT:Line:3"),
+        Route::get(GEOCODER, fixture("geocoder_oslo.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_line_12.json"),
+        )
+        .containing("NSR:StopPlace:59872"),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &[
+            "line",
+            "3",
+            "--pick",
+            "2",
+            "--stop",
+            "Oslo",
+            "--stop-pick",
+            "1",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let requests = server.requests();
+    let line = requests
+        .iter()
+        .find(|request| request.body.contains("query Line("))
+        .unwrap();
+    let stop_place = requests
+        .iter()
+        .find(|request| request.body.contains("query StopPlace("))
+        .unwrap();
+    assert_eq!(line.json()["variables"]["id"], "RUT:Line:3");
+    assert_eq!(stop_place.json()["variables"]["id"], "NSR:StopPlace:59872");
+}
+
+#[test]
+fn line_pick_is_used_even_with_an_exact_match() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_atb.json")),
+        Route::graphql(JOURNEY_PLANNER, "Line", fixture("line_12.json"))
+            .containing("ATB:Line:2_36"),
+    ]);
+
+    let output = busterminal(&server, &["line", "3", "--pick", "2"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let line = server
+        .requests()
+        .into_iter()
+        .find(|request| request.body.contains("query Line("))
+        .unwrap();
+    assert_eq!(line.json()["variables"]["id"], "ATB:Line:2_36");
+}
+
+#[test]
+fn ambiguous_line_without_terminal_fails_instead_of_prompting() {
+    let server = MockEntur::start(line_routes());
+
+    let output = busterminal(&server, &["line", "3"]);
+    let stderr = stderr(&output);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("2 - 3 Mortensrud - Bergkrystallen (Ruter) · metro"));
+    assert!(stderr.contains("'3' matches 2 lines and stdin is not a terminal"));
+}
+
+#[test]
+fn vehicles_for_line_code() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_all.json")),
+        Route::graphql(VEHICLES, "Vehicles", fixture("vehicles_12.json"))
+            .containing("ATB:Line:2_12"),
+        Route::graphql(JOURNEY_PLANNER, "Quays", fixture("quays_12.json")),
+    ]);
+
+    let output = busterminal(&server, &["vehicles", "--line", "12"]);
+    let stdout = stdout(&output);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout.starts_with(
+        " 12  Dragvoll\n     Next stop: Dragvoll (2)\n     -1 min · updated 9 min ago\n"
+    ));
+    assert!(
+        stdout.contains(" 12  Lade via sentrum\n     At stop: Tyholt (1)\n     +3 min · updated ")
+    );
+    assert!(stdout.contains("ATB:ServiceJourney:12_230306097863631_114 · vehicle 1734"));
+}
+
+#[test]
+fn vehicles_as_ndjson() {
+    let server = MockEntur::start(vec![
+        Route::graphql(VEHICLES, "Vehicles", fixture("vehicles_12.json")),
+        Route::graphql(JOURNEY_PLANNER, "Quays", fixture("quays_12.json")),
+    ]);
+
+    let output = busterminal(
+        &server,
+        &["vehicles", "--line", "ATB:Line:2_12", "--format", "ndjson"],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let vehicles: Vec<serde_json::Value> = output
+        .stdout
+        .split(|&byte| byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+    assert_eq!(vehicles.len(), 2);
+    assert_eq!(vehicles[1]["nextStop"]["id"], "NSR:Quay:73976");
+    assert_eq!(vehicles[1]["nextStop"]["name"], "Tyholt");
+    assert_eq!(vehicles[1]["nextStop"]["vehicleAtStop"], true);
+    assert_eq!(vehicles[1]["line"]["publicCode"], "12");
+    assert_eq!(
+        vehicles[1]["serviceJourneyId"],
+        "ATB:ServiceJourney:12_230306097863631_114"
+    );
+    assert_eq!(vehicles[1]["delay"], 185.0);
+    assert_eq!(vehicles[1]["delayMinutes"], 3);
+    assert!(vehicles[1].get("nextQuay").is_none());
+}
+
+#[test]
+fn line_without_vehicles_exits_with_no_match() {
+    let server = MockEntur::start(vec![Route::graphql(
+        VEHICLES,
+        "Vehicles",
+        fixture("vehicles_empty.json"),
+    )]);
+
+    let output = busterminal(&server, &["vehicles", "--line", "ATB:Line:2_12"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(
+        stderr(&output).contains("No vehicles are reporting their position on line: ATB:Line:2_12")
+    );
+}
@@ -0,0 +1,773 @@
+mod common;
+
+use std::time::Duration;
+
+use busterminal::cache::Cache;
+use busterminal::departure::DepartureOptions;
+use busterminal::filter::DepartureFilter;
+use busterminal::model::Mode;
+use busterminal::trip::TripOptions;
+use busterminal::{BusterminalError, EnTurClient};
+use chrono::DateTime;
+use common::{MockEntur, Route, TempDir, fixture};
+
+const JOURNEY_PLANNER: &str = "/journey-planner/v3/graphql";
+const GEOCODER: &str = "/geocoder/v1/autocomplete";
+const STOP_PLACES: &str = "/stop-places/v1/graphql";
+const VEHICLES: &str = "/realtime/v2/vehicles/graphql";
+
+#[tokio::test]
+async fn geocoder_query_is_url_encoded() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_tyholt.json"))]);
+
+    let geocode = server
+        .client()
+        .get_autocomplete_stop_name("Tyholt & \"co\"")
+        .await
+        .unwrap();
+
+    assert_eq!(geocode.features[0].properties.id, "NSR:StopPlace:42660");
+
+    let requests = server.requests();
+    assert!(requests[0].query.contains("text=Tyholt & \"co\""));
+    assert!(requests[0].query.contains("layers=venue"));
+}
+
+#[tokio::test]
+async fn stop_place_is_requested_with_variables() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_tyholt.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:\"42660\"",
+            &DepartureOptions {
+                start_time: Some(DateTime::parse_from_rfc3339("2099-01-01T08:00:00Z").unwrap()),
+                window: Some(Duration::from_secs(2 * 60 * 60)),
+                limit: Some(10),
+                ..Default::default()
+            },
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(stop_place.name, "Tyholt");
+    assert_eq!(stop_place.estimatedCalls.len(), 2);
+
+    let body = server.requests()[0].json();
+    assert_eq!(body["variables"]["id"], "NSR:StopPlace:\"42660\"");
+    assert_eq!(body["variables"]["startTime"], "2099-01-01T08:00:00+00:00");
+    assert_eq!(body["variables"]["timeRange"], 7200);
+    assert_eq!(body["variables"]["numberOfDepartures"], 10);
+    assert!(body["variables"].get("arrivalDeparture").is_none());
+}
+
+#[tokio::test]
+async fn trip_is_requested_with_places() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Trip",
+        fixture("trip_oslo_gardermoen.json"),
+    )]);
+
+    let trip = server
+        .client()
+        .plan_trip(
+            "NSR:StopPlace:59872",
+            "NSR:StopPlace:58211",
+            &TripOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(trip.trip.tripPatterns.len(), 1);
+
+    let body = server.requests()[0].json();
+    assert_eq!(body["variables"]["from"]["place"], "NSR:StopPlace:59872");
+    assert_eq!(body["variables"]["to"]["place"], "NSR:StopPlace:58211");
+    assert!(body["variables"].get("dateTime").is_none());
+}
+
+#[tokio::test]
+async fn trip_is_requested_for_arrival_time() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Trip",
+        fixture("trip_oslo_gardermoen.json"),
+    )]);
+
+    let options = TripOptions {
+        date_time: Some(DateTime::parse_from_rfc3339("2099-01-01T08:30:00+01:00").unwrap()),
+        arrive_by: true,
+        ..Default::default()
+    };
+
+    server
+        .client()
+        .plan_trip("NSR:StopPlace:59872", "NSR:StopPlace:58211", &options)
+        .await
+        .unwrap();
+
+    let body = server.requests()[0].json();
+    assert_eq!(body["variables"]["dateTime"], "2099-01-01T08:30:00+01:00");
+    assert_eq!(body["variables"]["arriveBy"], true);
+}
+
+#[tokio::test]
+async fn trip_preferences_are_sent_as_variables() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Trip",
+        fixture("trip_oslo_gardermoen.json"),
+    )]);
+
+    let options = TripOptions {
+        modes: vec![Mode::rail, Mode::bus],
+        max_transfers: Some(1),
+        walk_speed: Some(1.5),
+        wheelchair: true,
+        prefer_fewer_transfers: true,
+        max_walk_distance: Some(100.0),
+        ..Default::default()
+    };
+
+    let trip = server
+        .client()
+        .plan_trip("NSR:StopPlace:59872", "NSR:StopPlace:58211", &options)
+        .await
+        .unwrap();
+
+    // The only pattern walks 120.5 meters.
+    assert!(trip.trip.tripPatterns.is_empty());
+
+    let variables = &server.requests()[0].json()["variables"];
+    assert_eq!(
+        variables["modes"],
+        serde_json::json!({
+            "transportModes": [{ "transportMode": "rail" }, { "transportMode": "bus" }]
+        })
+    );
+    assert_eq!(variables["maximumTransfers"], 1);
+    assert_eq!(variables["walkSpeed"], 1.5);
+    assert_eq!(variables["wheelchairAccessible"], true);
+    assert!(variables["transferPenalty"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn longest_walk_fetches_later_pages_until_enough_patterns_are_left() {
+    let server = MockEntur::start(vec![
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen_later.json"),
+        )
+        .containing("MXxORVhUX1BBR0V8MjA5OS0wMS0wMVQwODoxMA=="),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+
+    let options = TripOptions {
+        max_walk_distance: Some(150.0),
+        count: Some(2),
+        ..Default::default()
+    };
+
+    let trip = server
+        .client()
+        .plan_trip("NSR:StopPlace:59872", "NSR:StopPlace:58211", &options)
+        .await
+        .unwrap();
+
+    assert_eq!(trip.trip.tripPatterns.len(), 2);
+    assert!(trip.trip.nextPageCursor.is_none());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn excluding_every_mode_is_rejected() {
+    let server = MockEntur::start(Vec::new());
+
+    let options = TripOptions {
+        modes: vec![Mode::bus],
+        exclude_modes: vec![Mode::bus],
+        ..Default::default()
+    };
+
+    let error = server
+        .client()
+        .plan_trip("NSR:StopPlace:59872", "NSR:StopPlace:58211", &options)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BusterminalError::InvalidConfig(_)));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn situations_are_requested_and_deduplicated() {
+    let server = MockEntur::start(vec![
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_oslo.json"),
+        ),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "Trip",
+            fixture("trip_oslo_gardermoen.json"),
+        ),
+    ]);
+    let client = server.client();
+
+    let stop_place = client
+        .get_stop_place(
+            "NSR:StopPlace:59872",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+    let trip = client
+        .plan_trip(
+            "NSR:StopPlace:59872",
+            "NSR:StopPlace:58211",
+            &TripOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    let situations = &stop_place.estimatedCalls[0].situations;
+    assert_eq!(situations.len(), 1);
+    assert_eq!(
+        situations[0].summary_text(),
+        Some("Track work between Oslo S and Lillestrøm")
+    );
+    assert_eq!(trip.trip.tripPatterns[0].legs[1].situations.len(), 1);
+
+    for request in server.requests() {
+        assert!(request.body.contains("situations {"));
+    }
+}
+
+#[tokio::test]
+async fn journey_is_requested_for_date() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "ServiceJourney",
+        fixture("service_journey_12.json"),
+    )]);
+
+    let journey = server
+        .client()
+        .journey("ATB:ServiceJourney:12_230306097863631_113", "2099-01-01")
+        .await
+        .unwrap();
+
+    assert_eq!(journey.line.publicCode, "12");
+    assert_eq!(journey.estimatedCalls.len(), 4);
+    assert_eq!(
+        journey.estimatedCalls[1].actualDepartureTime.as_deref(),
+        Some("2099-01-01T12:08:00+01:00")
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, JOURNEY_PLANNER);
+    assert_eq!(
+        request.json()["variables"]["id"],
+        "ATB:ServiceJourney:12_230306097863631_113"
+    );
+    assert_eq!(request.json()["variables"]["date"], "2099-01-01");
+}
+
+#[tokio::test]
+async fn unknown_journey_is_no_match() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "ServiceJourney",
+        fixture("service_journey_unknown.json"),
+    )]);
+
+    let error = server
+        .client()
+        .journey("ATB:ServiceJourney:nope", "2099-01-01")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BusterminalError::NoMatch(_)));
+    assert_eq!(error.exit_code(), 3);
+}
+
+#[tokio::test]
+async fn graphql_errors_are_surfaced() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("graphql_error.json"),
+    )]);
+
+    let error = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:42660",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap_err();
+
+    let BusterminalError::GraphQL(errors) = &error else {
+        panic!("expected a GraphQL error, got {:?}", error);
+    };
+    assert_eq!(errors.len(), 1);
+    assert!(error.to_string().contains("(at stopPlace.estimatedCalls)"));
+    assert_eq!(error.exit_code(), 6);
+}
+
+#[tokio::test]
+async fn http_errors_are_surfaced() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, String::from("{}")).status(503)]);
+
+    let error = server
+        .client()
+        .get_autocomplete_stop_name("Tyholt")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        BusterminalError::HttpStatus { status, .. } if status.as_u16() == 503
+    ));
+    assert_eq!(error.exit_code(), 5);
+}
+
+#[tokio::test]
+async fn unknown_stop_is_no_match() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_empty.json"))]);
+
+    let error = server
+        .client()
+        .get_autocomplete_stop_name("Nowhere")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BusterminalError::NoMatch(_)));
+    assert_eq!(error.exit_code(), 3);
+}
+
+#[tokio::test]
+async fn unreachable_server_is_transport_error() {
+    let client = busterminal::EnTurClient::builder()
+        .base_url("http://127.0.0.1:1")
+        .build()
+        .unwrap();
+
+    let error = client
+        .get_autocomplete_stop_name("Tyholt")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BusterminalError::Transport(_)));
+    assert_eq!(error.exit_code(), 4);
+}
+
+#[tokio::test]
+async fn client_name_is_sent() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_tyholt.json"))]);
+
+    server
+        .client()
+        .get_autocomplete_stop_name("Tyholt")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.requests()[0].header("et-client-name"),
+        Some("busterminal-tests")
+    );
+}
+
+#[tokio::test]
+async fn geocoder_results_are_cached_by_query() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_tyholt.json"))]);
+    let dir = TempDir::new("geocoder");
+    let client = server.cached_client(Cache::new(dir.path()));
+
+    let first = client.get_autocomplete_stop_name("Tyholt").await.unwrap();
+    let second = client.get_autocomplete_stop_name("tyholt").await.unwrap();
+    client.get_autocomplete_place("Tyholt").await.unwrap();
+
+    assert_eq!(first.features[0].properties.id, "NSR:StopPlace:42660");
+    assert_eq!(second.features[0].properties.id, "NSR:StopPlace:42660");
+    // Searching addresses as well is a different query.
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stop_info_shares_the_cache() {
+    let server = MockEntur::start(vec![Route::graphql(
+        STOP_PLACES,
+        "StopInfo",
+        fixture("stop_info_adjacent.json"),
+    )]);
+    let dir = TempDir::new("stop-info");
+
+    let client = server.cached_client(Cache::new(dir.path()));
+    client.get_stop_info("NSR:StopPlace:42661").await.unwrap();
+    client.get_stop_info("NSR:StopPlace:42661").await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    let client = server.cached_client(Cache::new(dir.path()).refresh(true));
+    let stop_place = client.get_stop_info("NSR:StopPlace:42661").await.unwrap();
+    assert_eq!(stop_place.name(), "Tyholttårnet");
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stale_entries_are_used_when_the_api_is_unreachable() {
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_tyholt.json"))]);
+    let dir = TempDir::new("stale");
+    server
+        .cached_client(Cache::new(dir.path()))
+        .get_autocomplete_stop_name("Tyholt")
+        .await
+        .unwrap();
+
+    let client = EnTurClient::builder()
+        .base_url("http://127.0.0.1:1")
+        .cache(Cache::new(dir.path()).ttl(Duration::ZERO))
+        .build()
+        .unwrap();
+    let geocode = client.get_autocomplete_stop_name("Tyholt").await.unwrap();
+
+    assert_eq!(geocode.features[0].properties.name, "Tyholt");
+
+    let error = client.get_autocomplete_stop_name("Lade").await.unwrap_err();
+    assert!(matches!(error, BusterminalError::Transport(_)));
+}
+
+#[tokio::test]
+async fn stale_entries_are_used_when_the_api_fails() {
+    let dir = TempDir::new("unavailable");
+    let server = MockEntur::start(vec![Route::get(GEOCODER, fixture("geocoder_tyholt.json"))]);
+    server
+        .cached_client(Cache::new(dir.path()))
+        .get_autocomplete_stop_name("Tyholt")
+        .await
+        .unwrap();
+
+    let down = MockEntur::start(vec![Route::get(GEOCODER, String::from("{}")).status(503)]);
+    let client = down.cached_client(Cache::new(dir.path()).ttl(Duration::ZERO));
+
+    let geocode = client.get_autocomplete_stop_name("Tyholt").await.unwrap();
+    assert_eq!(geocode.features[0].properties.name, "Tyholt");
+    assert_eq!(down.requests().len(), 1);
+
+    let error = client.get_autocomplete_place("Tyholt").await.unwrap_err();
+    assert!(matches!(
+        error,
+        BusterminalError::HttpStatus { status, .. } if status.as_u16() == 503
+    ));
+}
+
+#[tokio::test]
+async fn nearby_asks_for_the_same_call_fields_as_departures() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Nearest", fixture("nearest_oslo.json")),
+        Route::graphql(
+            JOURNEY_PLANNER,
+            "StopPlace",
+            fixture("stop_place_tyholt.json"),
+        ),
+    ]);
+    let client = server.client();
+
+    client
+        .get_nearby_stop_places(59.9111, 10.7528, 300.0, "2099-01-01T08:00:00+01:00")
+        .await
+        .unwrap();
+    client
+        .get_stop_place(
+            "NSR:StopPlace:42660",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let queries: Vec<String> = server
+        .requests()
+        .iter()
+        .map(|request| request.json()["query"].as_str().unwrap().to_string())
+        .collect();
+    let fragment = |query: &str| {
+        let start = query.find("fragment departureCall").unwrap();
+        let end = query.find("fragment situation").unwrap();
+        query[start..end].to_string()
+    };
+
+    assert_eq!(fragment(&queries[0]), fragment(&queries[1]));
+    for query in &queries {
+        assert_eq!(query.matches("fragment situation on").count(), 1);
+    }
+}
+
+#[tokio::test]
+async fn arrivals_hide_calls_without_alighting() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_arrivals.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:41742",
+            &DepartureOptions {
+                arrivals: true,
+                ..Default::default()
+            },
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let body = server.requests()[0].json();
+    assert_eq!(body["variables"]["arrivalDeparture"], "arrivals");
+    assert!(body["query"].as_str().unwrap().contains("forAlighting"));
+
+    assert_eq!(stop_place.estimatedCalls.len(), 2);
+    assert_eq!(
+        stop_place.estimatedCalls[0].origin().unwrap().name,
+        "Værnes"
+    );
+    assert_eq!(
+        stop_place.estimatedCalls[0].arrival_delay_minutes(),
+        Some(4)
+    );
+    assert!(
+        stop_place
+            .estimatedCalls
+            .iter()
+            .all(|call| call.forAlighting)
+    );
+}
+
+#[tokio::test]
+async fn departures_hide_calls_without_boarding() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "StopPlace",
+        fixture("stop_place_arrivals.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:41742",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(stop_place.estimatedCalls.len(), 2);
+    assert!(
+        stop_place
+            .estimatedCalls
+            .iter()
+            .all(|call| call.forBoarding)
+    );
+}
+
+#[tokio::test]
+async fn line_is_requested_by_id() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Line",
+        fixture("line_12.json"),
+    )]);
+
+    let line = server.client().get_line("ATB:Line:2_12").await.unwrap();
+
+    assert_eq!(line.public_code(), "12");
+    assert_eq!(line.operator.unwrap().name, "Vy Buss");
+    assert_eq!(line.journeyPatterns.len(), 2);
+    assert_eq!(line.journeyPatterns[0].quays[1].name, "Tyholt");
+    assert_eq!(
+        server.requests()[0].json()["variables"]["id"],
+        "ATB:Line:2_12"
+    );
+}
+
+#[tokio::test]
+async fn unknown_line_is_no_match() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Line",
+        fixture("line_unknown.json"),
+    )]);
+
+    let error = server.client().get_line("ATB:Line:nope").await.unwrap_err();
+
+    assert!(matches!(error, BusterminalError::NoMatch(_)));
+}
+
+#[tokio::test]
+async fn lines_with_the_exact_code_are_asked_for_first() {
+    let server = MockEntur::start(vec![Route::graphql(
+        JOURNEY_PLANNER,
+        "Lines",
+        fixture("lines_3.json"),
+    )]);
+
+    let lines = server.client().find_lines(" 3 ", None).await.unwrap();
+    let ids: Vec<&str> = lines.iter().map(|line| line.id.as_str()).collect();
+
+    assert_eq!(ids, ["ATB:Line:2_3", "RUT:Line:3"]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].json()["variables"]["publicCode"], "3");
+    assert!(requests[0].json()["variables"].get("authorities").is_none());
+}
+
+#[tokio::test]
+async fn lines_are_ranked_by_how_well_the_code_matches() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_empty.json"))
+            .containing("\"publicCode\""),
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_all.json")),
+    ]);
+
+    let lines = server.client().find_lines("3", None).await.unwrap();
+    let ids: Vec<&str> = lines.iter().map(|line| line.id.as_str()).collect();
+
+    assert_eq!(
+        ids,
+        ["ATB:Line:2_3", "RUT:Line:3", "RUT:Line:31", "ATB:Line:2_36"]
+    );
+    assert!(
+        server.requests()[1].json()["variables"]
+            .get("publicCode")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn lines_can_be_limited_to_a_region() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Authorities", fixture("authorities.json")),
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_atb.json"))
+            .containing("ATB:Authority:2"),
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_ruter.json"))
+            .containing("RUT:Authority:RUT"),
+    ]);
+    let client = server.client();
+
+    let by_codespace = client.find_lines("3", Some("rut")).await.unwrap();
+    let by_authority = client.find_lines("lade", Some("AtB")).await.unwrap();
+    let no_line = client.find_lines("12", Some("Ruter")).await.unwrap_err();
+    let no_region = client.find_lines("12", Some("Nowhere")).await.unwrap_err();
+
+    assert_eq!(by_codespace.len(), 2);
+    assert_eq!(by_codespace[0].id, "RUT:Line:3");
+    assert_eq!(by_authority.len(), 2);
+    assert!(matches!(no_line, BusterminalError::NoMatch(_)));
+    assert!(matches!(no_region, BusterminalError::NoMatch(_)));
+    assert!(no_region.to_string().contains("Unknown region: Nowhere"));
+
+    let lines = server
+        .requests()
+        .into_iter()
+        .find(|request| request.body.contains("query Lines("))
+        .unwrap();
+    assert_eq!(
+        lines.json()["variables"]["authorities"],
+        serde_json::json!(["RUT:Authority:RUT"])
+    );
+}
+
+#[tokio::test]
+async fn lines_are_cached() {
+    let server = MockEntur::start(vec![
+        Route::graphql(JOURNEY_PLANNER, "Authorities", fixture("authorities.json")),
+        Route::graphql(JOURNEY_PLANNER, "Lines", fixture("lines_ruter.json")),
+    ]);
+    let dir = TempDir::new("lines");
+    let client = server.cached_client(Cache::new(dir.path()));
+
+    client.find_lines("31", Some("Ruter")).await.unwrap();
+    client.find_lines("31", Some("RUT")).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn line_without_vehicles_is_no_match() {
+    let server = MockEntur::start(vec![Route::graphql(
+        VEHICLES,
+        "Vehicles",
+        fixture("vehicles_empty.json"),
+    )]);
+
+    let error = server.client().vehicles("ATB:Line:2_12").await.unwrap_err();
+
+    assert!(matches!(error, BusterminalError::NoMatch(_)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn vehicle_api_errors_are_surfaced() {
+    let server = MockEntur::start(vec![
+        Route::graphql(VEHICLES, "Vehicles", String::from("{}")).status(503),
+    ]);
+
+    let error = server.client().vehicles("ATB:Line:2_12").await.unwrap_err();
+
+    assert_eq!(error.exit_code(), 5);
+}
+
+#[tokio::test]
+async fn vehicles_are_kept_when_quay_lookup_fails() {
+    let server = MockEntur::start(vec![
+        Route::graphql(VEHICLES, "Vehicles", fixture("vehicles_12.json")),
+        Route::graphql(JOURNEY_PLANNER, "Quays", String::from("{}")).status(503),
+    ]);
+
+    let vehicles = server.client().vehicles("ATB:Line:2_12").await.unwrap();
+
+    assert_eq!(vehicles.len(), 2);
+    assert!(vehicles.iter().all(|vehicle| vehicle.nextQuay.is_none()));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stop_details_skip_related_sites_that_are_gone() {
+    let server = MockEntur::start(vec![
+        Route::graphql(STOP_PLACES, "StopInfo", fixture("stop_info_parent.json"))
+            .containing("NSR:StopPlace:60000"),
+        Route::graphql(STOP_PLACES, "StopInfo", fixture("stop_info_empty.json"))
+            .containing("NSR:StopPlace:42661"),
+        Route::graphql(STOP_PLACES, "StopInfo", fixture("stop_info_tyholt.json"))
+            .containing("NSR:StopPlace:42660"),
+    ]);
+
+    let info = server
+        .client()
+        .get_stop_details("NSR:StopPlace:42660")
+        .await
+        .unwrap();
+
+    assert_eq!(info.stopPlace.id, "NSR:StopPlace:42660");
+    assert_eq!(info.parent.unwrap().id, "NSR:StopPlace:60000");
+    assert!(info.adjacent.is_empty());
+}
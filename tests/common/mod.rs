@@ -0,0 +1,308 @@
+//! A small stand-in for the Entur APIs serving recorded responses from
+//! `tests/fixtures`, so the client can be exercised without network access.
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use busterminal::EnTurClient;
+use busterminal::cache::Cache;
+use chrono::{Duration, Local};
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).expect("request body is JSON")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    method: &'static str,
+    path: &'static str,
+    operation: Option<String>,
+    contains: Vec<String>,
+    status: u16,
+    body: String,
+}
+
+impl Route {
+    pub fn get(path: &'static str, body: String) -> Self {
+        Self {
+            method: "GET",
+            path,
+            operation: None,
+            contains: Vec::new(),
+            status: 200,
+            body,
+        }
+    }
+
+    /// Matches a GraphQL request by its operation name, e.g. `StopPlace`.
+    pub fn graphql(path: &'static str, operation: &str, body: String) -> Self {
+        Self {
+            method: "POST",
+            path,
+            operation: Some(operation.to_string()),
+            contains: Vec::new(),
+            status: 200,
+            body,
+        }
+    }
+
+    /// Only match requests whose query string or body contains `text`.
+    pub fn containing(mut self, text: &str) -> Self {
+        self.contains.push(text.to_string());
+        self
+    }
+
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    fn matches(&self, request: &RecordedRequest) -> bool {
+        self.method == request.method
+            && self.path == request.path
+            && self.operation.as_ref().is_none_or(|operation| {
+                // With or without variables: `query Line($id: ID!)`, `query Lines {`.
+                request.body.contains(&format!("query {}(", operation))
+                    || request.body.contains(&format!("query {} {{", operation))
+            })
+            && self
+                .contains
+                .iter()
+                .all(|text| request.query.contains(text) || request.body.contains(text))
+    }
+}
+
+pub struct MockEntur {
+    url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockEntur {
+    pub fn start(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+
+                handle(stream, &routes, &recorded);
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn client(&self) -> EnTurClient {
+        EnTurClient::builder()
+            .base_url(&self.url)
+            .client_name("busterminal-tests")
+            .build()
+            .expect("client against mock server")
+    }
+
+    /// Like [`MockEntur::client`], with an on-disk cache.
+    pub fn cached_client(&self, cache: Cache) -> EnTurClient {
+        EnTurClient::builder()
+            .base_url(&self.url)
+            .client_name("busterminal-tests")
+            .cache(cache)
+            .build()
+            .expect("client against mock server")
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn handle(stream: TcpStream, routes: &[Route], recorded: &Mutex<Vec<RecordedRequest>>) {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut headers = Vec::new();
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+
+    let request = RecordedRequest {
+        method,
+        path: path.to_string(),
+        query: percent_decode(query),
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    };
+
+    let (status, body) = match routes.iter().find(|route| route.matches(&request)) {
+        Some(route) => (route.status, route.body.clone()),
+        None => (404, String::from("{}")),
+    };
+
+    recorded.lock().unwrap().push(request);
+
+    let response = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    let mut stream = reader.into_inner();
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.replace('+', " ").into_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = String::from_utf8_lossy(&bytes[i + 1..i + 3]);
+            if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// An empty directory of its own under the system temp directory, for config
+/// files and caches. It is removed again when dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        static DIRS: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "busterminal-{}-{}-{}",
+            name,
+            std::process::id(),
+            DIRS.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&path);
+
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Reads a recorded response from `tests/fixtures`. Placeholders of the form
+/// `{{now+5m}}` are replaced with a timestamp that many minutes from now.
+pub fn fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    let mut text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("read {}: {}", path.display(), error));
+
+    while let Some(start) = text.find("{{now+") {
+        let end = start + text[start..].find("m}}").expect("unterminated placeholder");
+        let minutes: i64 = text[start + 6..end]
+            .parse()
+            .expect("minutes in placeholder");
+        let time = Local::now() + Duration::minutes(minutes) + Duration::seconds(30);
+
+        text.replace_range(start..end + 3, &time.to_rfc3339());
+    }
+
+    text
+}
+
+/// Removes ANSI escape sequences so assertions can focus on the text.
+pub fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+
+    plain
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::fs;
+
+use busterminal::BusterminalError;
+use busterminal::config::{Config, Favourite};
+use busterminal::model::Location;
+use common::TempDir;
+
+fn tyholt() -> Favourite {
+    Favourite {
+        id: String::from("NSR:StopPlace:42660"),
+        name: String::from("Tyholt"),
+        latitude: None,
+        longitude: None,
+    }
+}
+
+#[test]
+fn missing_file_is_an_empty_config() {
+    let dir = TempDir::new("missing");
+    let config = Config::load(&dir.join("config.toml")).unwrap();
+
+    assert!(config.favourites.is_empty());
+}
+
+#[test]
+fn favourites_survive_a_round_trip() {
+    let dir = TempDir::new("round-trip");
+    let path = dir.join("busterminal").join("config.toml");
+    let mut config = Config::default();
+    config.favourites.insert(String::from("home"), tyholt());
+    config.favourites.insert(
+        String::from("work"),
+        Favourite {
+            id: String::from("KVE:TopographicPlace:0301-Karl Johans gate 22"),
+            name: String::from("Karl Johans gate 22"),
+            latitude: Some(59.91264),
+            longitude: Some(10.745121),
+        },
+    );
+
+    config.save(&path).unwrap();
+
+    assert_eq!(Config::load(&path).unwrap(), config);
+    assert!(
+        fs::read_to_string(&path)
+            .unwrap()
+            .contains("[favourites.home]")
+    );
+}
+
+#[test]
+fn favourites_are_found_with_or_without_at() {
+    let mut config = Config::default();
+    config.favourites.insert(String::from("home"), tyholt());
+
+    assert_eq!(config.favourite("@home").unwrap(), &tyholt());
+    assert_eq!(config.favourite("home").unwrap(), &tyholt());
+
+    let error = config.favourite("@work").unwrap_err();
+    assert!(matches!(error, BusterminalError::NoMatch(_)));
+    assert_eq!(
+        error.to_string(),
+        "Unknown favourite: @work. Save it with `busterminal fav add work`"
+    );
+}
+
+#[test]
+fn stops_travel_by_id_and_addresses_by_coordinates() {
+    assert_eq!(
+        tyholt().location(),
+        Location::Place(String::from("NSR:StopPlace:42660"))
+    );
+
+    let address = Favourite {
+        latitude: Some(59.91264),
+        longitude: Some(10.745121),
+        ..tyholt()
+    };
+    assert!(!address.is_stop());
+    assert_eq!(
+        address.location(),
+        Location::Coordinates {
+            latitude: 59.91264_f32.into(),
+            longitude: 10.745121_f32.into(),
+            name: Some(String::from("Tyholt")),
+        }
+    );
+}
+
+#[test]
+fn invalid_file_is_a_config_error() {
+    let dir = TempDir::new("invalid");
+    let path = dir.join("busterminal").join("config.toml");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "[favourites.home]\nid = 42\n").unwrap();
+
+    let error = Config::load(&path).unwrap_err();
+
+    assert!(matches!(error, BusterminalError::InvalidConfig(_)));
+    assert_eq!(error.exit_code(), 2);
+}
@@ -0,0 +1,119 @@
+use busterminal::departure::{GroupBy, group_departures};
+use busterminal::model::EstimatedCall;
+use serde_json::json;
+
+fn call(quay: &str, line: &str, mode: &str, destination: &str, minute: u32) -> EstimatedCall {
+    serde_json::from_value(json!({
+        "realtime": false,
+        "aimedDepartureTime": format!("2099-01-01T08:{:02}:00+01:00", minute),
+        "expectedDepartureTime": format!("2099-01-01T08:{:02}:00+01:00", minute),
+        "date": "2099-01-01",
+        "forBoarding": true,
+        "destinationDisplay": { "frontText": destination },
+        "quay": {
+            "id": format!("NSR:Quay:{}", quay),
+            "name": "Oslo S",
+            "publicCode": quay,
+            "description": null
+        },
+        "serviceJourney": {
+            "id": format!("RUT:ServiceJourney:{}-{}", line, minute),
+            "journeyPattern": {
+                "line": {
+                    "id": format!("RUT:Line:{}", line),
+                    "publicCode": line,
+                    "name": format!("Line {}", line),
+                    "transportMode": mode
+                }
+            }
+        }
+    }))
+    .unwrap()
+}
+
+fn board() -> Vec<EstimatedCall> {
+    vec![
+        call("10", "L1", "rail", "Lillestrøm", 1),
+        call("2", "R10", "rail", "Drammen", 2),
+        call("10", "L1", "rail", "Lillestrøm", 3),
+        call("2", "L1", "rail", "Spikkestad", 4),
+        call("10", "L1", "rail", "Lillestrøm", 5),
+        call("2", "R10", "rail", "Drammen", 6),
+    ]
+}
+
+fn titles(groups: &[busterminal::departure::DepartureGroup]) -> Vec<String> {
+    groups.iter().map(|group| group.title.clone()).collect()
+}
+
+#[test]
+fn quays_are_in_platform_order() {
+    let departures = board();
+
+    let groups = group_departures(&departures, GroupBy::Quay, 2);
+
+    assert_eq!(titles(&groups), ["Spor 2", "Spor 10"]);
+    assert_eq!(groups[0].departures.len(), 2);
+    assert_eq!(
+        groups[0].departures[0].destinationDisplay.frontText,
+        "Drammen"
+    );
+    assert_eq!(
+        groups[0].departures[1].destinationDisplay.frontText,
+        "Spikkestad"
+    );
+}
+
+#[test]
+fn lines_keep_the_next_departures() {
+    let departures = board();
+
+    let groups = group_departures(&departures, GroupBy::Line, 3);
+
+    assert_eq!(titles(&groups), ["L1 Line L1", "R10 Line R10"]);
+    assert_eq!(groups[0].departures.len(), 3);
+    assert_eq!(groups[1].departures.len(), 2);
+}
+
+#[test]
+fn directions_are_soonest_first() {
+    let departures = board();
+
+    let groups = group_departures(&departures, GroupBy::Direction, 1);
+
+    assert_eq!(
+        titles(&groups),
+        [
+            "Towards Lillestrøm",
+            "Towards Drammen",
+            "Towards Spikkestad"
+        ]
+    );
+    assert!(groups.iter().all(|group| group.departures.len() == 1));
+}
+
+#[test]
+fn quays_of_other_modes_are_platforms() {
+    let departures = vec![call("A", "31", "bus", "Tonsenhagen", 1)];
+
+    let groups = group_departures(&departures, GroupBy::Quay, 3);
+
+    assert_eq!(titles(&groups), ["Platform A"]);
+}
+
+#[test]
+fn grouping_parses_from_the_command_line() {
+    assert_eq!("quay".parse::<GroupBy>(), Ok(GroupBy::Quay));
+    assert_eq!("direction".parse::<GroupBy>(), Ok(GroupBy::Direction));
+    assert!("platform".parse::<GroupBy>().is_err());
+}
+
+#[test]
+fn empty_groups_keep_their_order() {
+    let departures = board();
+
+    let groups = group_departures(&departures, GroupBy::Quay, 0);
+
+    assert_eq!(titles(&groups), ["Spor 2", "Spor 10"]);
+    assert!(groups.iter().all(|group| group.departures.is_empty()));
+}
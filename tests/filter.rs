@@ -0,0 +1,175 @@
+mod common;
+
+use busterminal::departure::DepartureOptions;
+use busterminal::filter::DepartureFilter;
+use busterminal::model::{Mode, StopPlace};
+use common::{MockEntur, Route, fixture};
+
+async fn departures(server: &MockEntur, filter: &DepartureFilter) -> StopPlace {
+    server
+        .client()
+        .get_stop_place("NSR:StopPlace:59872", &DepartureOptions::default(), filter)
+        .await
+        .unwrap()
+}
+
+fn oslo() -> MockEntur {
+    MockEntur::start(vec![
+        Route::graphql(
+            "/journey-planner/v3/graphql",
+            "StopPlace",
+            fixture("stop_place_oslo.json"),
+        ),
+        Route::graphql(
+            "/journey-planner/v3/graphql",
+            "Lines",
+            fixture("lines_oslo.json"),
+        ),
+    ])
+}
+
+/// The variables of the departures query, after any line lookups.
+fn stop_place_variables(server: &MockEntur) -> serde_json::Value {
+    server
+        .requests()
+        .into_iter()
+        .find(|request| request.body.contains("query StopPlace("))
+        .unwrap()
+        .json()["variables"]
+        .clone()
+}
+
+fn destinations(stop_place: &StopPlace) -> Vec<&str> {
+    stop_place
+        .estimatedCalls
+        .iter()
+        .map(|call| call.destinationDisplay.frontText.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn no_filter_keeps_everything() {
+    let server = oslo();
+
+    let stop_place = departures(&server, &DepartureFilter::default()).await;
+
+    assert_eq!(stop_place.estimatedCalls.len(), 5);
+
+    let variables = &stop_place_variables(&server);
+    assert!(variables.get("whiteListed").is_none());
+    assert!(variables.get("whiteListedModes").is_none());
+}
+
+#[tokio::test]
+async fn public_line_codes_are_looked_up_and_sent_to_entur() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        lines: vec![String::from("l1"), String::from("31")],
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert_eq!(
+        destinations(&stop_place),
+        ["Lillestrøm", "Tonsenhagen", "Spikkestad"]
+    );
+    assert_eq!(
+        stop_place_variables(&server)["whiteListed"]["lines"],
+        serde_json::json!(["RUT:Line:31", "VYG:Line:L1"])
+    );
+}
+
+#[tokio::test]
+async fn unknown_public_line_code_leaves_no_departures() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        lines: vec![String::from("99")],
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert!(stop_place.estimatedCalls.is_empty());
+}
+
+#[tokio::test]
+async fn line_ids_are_sent_to_entur() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        lines: vec![String::from("RUT:Line:31")],
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert_eq!(destinations(&stop_place), ["Tonsenhagen"]);
+    assert_eq!(
+        stop_place_variables(&server)["whiteListed"]["lines"][0],
+        "RUT:Line:31"
+    );
+}
+
+#[tokio::test]
+async fn modes_are_sent_to_entur() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        modes: vec![Mode::bus, Mode::tram],
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert_eq!(destinations(&stop_place), ["Tonsenhagen", "Ljabru"]);
+    assert_eq!(
+        stop_place_variables(&server)["whiteListedModes"],
+        serde_json::json!(["bus", "tram"])
+    );
+}
+
+#[tokio::test]
+async fn quay_matches_platform_name_or_code() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        quays: vec![String::from("Spor 4"), String::from("b")],
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert_eq!(destinations(&stop_place), ["Lillestrøm", "Ljabru"]);
+}
+
+#[tokio::test]
+async fn quay_matches_group_heading() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        quays: vec![String::from("Platform B")],
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert_eq!(destinations(&stop_place), ["Ljabru"]);
+}
+
+#[tokio::test]
+async fn towards_matches_part_of_destination() {
+    let server = oslo();
+    let filter = DepartureFilter {
+        lines: vec![String::from("L1")],
+        towards: Some(String::from("lille")),
+        ..Default::default()
+    };
+
+    let stop_place = departures(&server, &filter).await;
+
+    assert_eq!(destinations(&stop_place), ["Lillestrøm"]);
+}
+
+#[test]
+fn modes_parse_case_insensitively() {
+    assert_eq!("Bus".parse::<Mode>(), Ok(Mode::bus));
+    assert_eq!(" tram".parse::<Mode>(), Ok(Mode::tram));
+    assert!("hovercraft".parse::<Mode>().is_err());
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use std::io::Cursor;
+
+use busterminal::model::Geocode;
+use busterminal::prompt::{self, Paging};
+use busterminal::render;
+use common::{fixture, strip_ansi};
+
+fn features(name: &str) -> Geocode {
+    serde_json::from_str(&fixture(name)).unwrap()
+}
+
+#[test]
+fn single_candidate_is_chosen_without_prompting() {
+    let geocode = features("geocoder_tyholt.json");
+    let mut out = Vec::new();
+
+    let feature = prompt::choose(
+        &mut Cursor::new(""),
+        &mut out,
+        None,
+        &geocode.features,
+        render::feature_choice,
+        "stop",
+    )
+    .unwrap();
+
+    assert_eq!(feature.properties.name, "Tyholt");
+    assert!(out.is_empty());
+}
+
+#[test]
+fn multiple_candidates_are_listed_and_chosen() {
+    let geocode = features("geocoder_oslo.json");
+    let mut out = Vec::new();
+
+    let feature = prompt::choose(
+        &mut Cursor::new("2\n"),
+        &mut out,
+        Some("Travel from"),
+        &geocode.features,
+        render::feature_choice,
+        "stop",
+    )
+    .unwrap();
+
+    assert_eq!(feature.properties.id, "NSR:StopPlace:4000");
+
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+    let expected = "\
+Travel from
+1 - Oslo S (Oslo - Oslo) · Stop
+2 - Oslo bussterminal (Oslo - Oslo) · Stop
+3 - Oslo lufthavn (Ullensaker - Akershus) · Stop
+
+
+? Which stop (1 - 3): ";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn invalid_choices_are_asked_again() {
+    let geocode = features("geocoder_oslo.json");
+    let mut out = Vec::new();
+
+    let feature = prompt::choose(
+        &mut Cursor::new("0\nthree\n3\n"),
+        &mut out,
+        None,
+        &geocode.features,
+        render::feature_choice,
+        "stop",
+    )
+    .unwrap();
+
+    assert_eq!(feature.properties.name, "Oslo lufthavn");
+
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+    assert_eq!(
+        output
+            .matches("X Invalid stop - pick another one (1 - 3): ")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn closed_input_is_an_error() {
+    let geocode = features("geocoder_oslo.json");
+
+    let error = prompt::choose(
+        &mut Cursor::new("7\n"),
+        &mut Vec::new(),
+        None,
+        &geocode.features,
+        render::feature_choice,
+        "stop",
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn paging_through_trips() {
+    let mut out = Vec::new();
+
+    let next = prompt::choose_page(&mut Cursor::new("n\n"), &mut out, true, true).unwrap();
+    let previous = prompt::choose_page(&mut Cursor::new("P\n"), &mut out, true, true).unwrap();
+    let quit = prompt::choose_page(&mut Cursor::new("\n"), &mut out, true, true).unwrap();
+    let eof = prompt::choose_page(&mut Cursor::new(""), &mut out, true, false).unwrap();
+    let unavailable = prompt::choose_page(&mut Cursor::new("p\n"), &mut out, true, false).unwrap();
+
+    assert_eq!(next, Paging::Next);
+    assert_eq!(previous, Paging::Previous);
+    assert_eq!(quit, Paging::Quit);
+    assert_eq!(eof, Paging::Quit);
+    assert_eq!(unavailable, Paging::Quit);
+    assert!(
+        strip_ansi(&String::from_utf8(out).unwrap())
+            .contains("? n: later, p: earlier, Enter: quit")
+    );
+}
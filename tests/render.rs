@@ -0,0 +1,366 @@
+mod common;
+
+use std::collections::HashSet;
+
+use busterminal::departure::DepartureOptions;
+use busterminal::filter::DepartureFilter;
+use busterminal::model::VehiclePosition;
+use busterminal::render;
+use busterminal::trip::TripOptions;
+use common::{MockEntur, Route, fixture, strip_ansi};
+
+#[tokio::test]
+async fn departures_show_line_destination_and_time() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "StopPlace",
+        fixture("stop_place_tyholt.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:42660",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut out = Vec::new();
+    render::print_departures(&mut out, &stop_place.estimatedCalls).unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], " 12  Lade via sentrum");
+    assert!(lines[1].starts_with(" ● 5 min ("), "{}", lines[1]);
+    assert!(lines[1].ends_with(") +2"), "{}", lines[1]);
+    assert_eq!(lines[3], " 12  Dragvoll");
+    assert_eq!(lines[4], " ○ Thu 01.01 12:34");
+}
+
+#[tokio::test]
+async fn arrivals_show_line_origin_and_arrival_time() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "StopPlace",
+        fixture("stop_place_arrivals.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:41742",
+            &DepartureOptions {
+                arrivals: true,
+                ..Default::default()
+            },
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut out = Vec::new();
+    render::print_arrivals(&mut out, &stop_place.estimatedCalls).unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], " R70  from Værnes");
+    assert!(lines[1].starts_with(" ● 6 min ("), "{}", lines[1]);
+    assert!(lines[1].ends_with(") +4"), "{}", lines[1]);
+    assert_eq!(lines[3], " R60  from Oppdal");
+    assert_eq!(lines[4], " ○ Thu 01.01 12:34");
+}
+
+#[test]
+fn arrivals_without_service_journey_have_no_origin() {
+    let arrivals: Vec<busterminal::model::EstimatedCall> =
+        serde_json::from_value(serde_json::json!([
+            {
+                "realtime": false,
+                "aimedDepartureTime": "2099-01-01T12:40:00+01:00",
+                "expectedDepartureTime": "2099-01-01T12:40:00+01:00",
+                "aimedArrivalTime": "2099-01-01T12:34:00+01:00",
+                "expectedArrivalTime": "2099-01-01T12:34:00+01:00",
+                "date": "2099-01-01",
+                "forBoarding": false,
+                "forAlighting": true,
+                "destinationDisplay": { "frontText": "Trondheim S" },
+                "quay": {
+                    "id": "NSR:Quay:71184",
+                    "name": "Trondheim S",
+                    "publicCode": "2",
+                    "description": null
+                },
+                "serviceJourney": null
+            }
+        ]))
+        .unwrap();
+
+    let mut out = Vec::new();
+    render::print_arrivals(&mut out, &arrivals).unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], " origin unknown");
+    assert_eq!(lines[1], " ○ Thu 01.01 12:34");
+    assert!(!output.contains("from Trondheim S"));
+}
+
+#[tokio::test]
+async fn board_highlights_changed_departures() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "StopPlace",
+        fixture("stop_place_tyholt.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:42660",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let changed = HashSet::from([stop_place.estimatedCalls[1].key()]);
+
+    let mut out = Vec::new();
+    render::print_board(
+        &mut out,
+        &stop_place.estimatedCalls,
+        &changed,
+        &chrono::Local::now(),
+    )
+    .unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("   12  Lade via sentrum"));
+    assert!(lines[0].contains(" ● 5 min ("), "{}", lines[0]);
+    assert!(!lines[0].contains("(updated)"));
+    assert!(
+        lines[1].ends_with("○ Thu 01.01 12:34 (updated)"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn departure_times_count_down_only_in_the_next_ten_minutes() {
+    let now = chrono::DateTime::parse_from_rfc3339("2099-01-01T14:00:00+01:00").unwrap();
+    let time = |text: &str| {
+        let departure = chrono::DateTime::parse_from_rfc3339(text).unwrap();
+        strip_ansi(&render::format_departure_time(&departure, &now))
+    };
+
+    assert_eq!(time("2099-01-01T14:05:00+01:00"), "5 min (14:05)");
+    assert_eq!(time("2099-01-01T14:30:00+01:00"), "14:30");
+    assert_eq!(time("2099-01-01T06:05:00+01:00"), "06:05");
+    assert_eq!(time("2099-01-02T00:15:00+01:00"), "Fri 02.01 00:15");
+}
+
+#[tokio::test]
+async fn departures_show_cancellations_and_notes() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "StopPlace",
+        fixture("stop_place_disruptions.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:42660",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut out = Vec::new();
+    render::print_departures(&mut out, &stop_place.estimatedCalls).unwrap();
+    let raw = String::from_utf8(out).unwrap();
+    let output = strip_ansi(&raw);
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], " 12  Lade via sentrum");
+    assert_eq!(lines[1], " Thu 01.01 12:40 Cancelled");
+    assert!(raw.contains("\x1b[9mLade via sentrum\x1b[0m"));
+    assert!(raw.contains("\x1b[9mThu 01.01 12:40\x1b[0m"));
+
+    assert_eq!(lines[3], " 12  Dragvoll");
+    assert_eq!(
+        lines[4],
+        " ● Thu 01.01 12:58 -2 (uncertain) · Standing room only"
+    );
+}
+
+#[tokio::test]
+async fn trip_shows_walking_and_transit_legs() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "Trip",
+        fixture("trip_oslo_gardermoen.json"),
+    )]);
+
+    let trip = server
+        .client()
+        .plan_trip(
+            "NSR:StopPlace:59872",
+            "NSR:StopPlace:58211",
+            &TripOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut out = Vec::new();
+    render::print_trip_patterns(&mut out, &trip.trip.tripPatterns).unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+
+    let expected = "\
+Travel time: 25 min
+
+      . 
+      . Walk 3 minutes
+      . 
+
+08:03 • Oslo S Spor 13 
+      |
+      |  FX  Oslo lufthavn
+      | 22 min
+      | ⚠ Normal: Reduced service
+      |   Flytoget runs every 20 minutes.
+      |   from 01.01 05:00
+      |
+      |
+08:25 • Oslo lufthavn  Spor 2 
+
+================================================================================
+
+";
+    assert_eq!(output, expected);
+}
+
+#[tokio::test]
+async fn situations_are_printed_once_beneath_departures() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "StopPlace",
+        fixture("stop_place_oslo.json"),
+    )]);
+
+    let stop_place = server
+        .client()
+        .get_stop_place(
+            "NSR:StopPlace:59872",
+            &DepartureOptions::default(),
+            &DepartureFilter::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut out = Vec::new();
+    render::print_departures(&mut out, &stop_place.estimatedCalls).unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], " L1  Lillestrøm");
+    assert_eq!(
+        lines[2],
+        " ⚠ Severe: Track work between Oslo S and Lillestrøm"
+    );
+    assert_eq!(
+        lines[3],
+        "   Buses replace trains between Oslo S and Lillestrøm."
+    );
+    assert_eq!(lines[4], "   01.01 06:00 – 03.01 23:59");
+    assert_eq!(lines[5], "");
+    assert!(output.contains(
+        " L1  Spikkestad\n ● Thu 01.01 08:05\n ⚠ Track work between Oslo S and Lillestrøm (see above)\n"
+    ));
+    assert_eq!(output.matches("Buses replace trains").count(), 1);
+}
+
+#[tokio::test]
+async fn journey_marks_passed_stops_and_vehicle() {
+    let server = MockEntur::start(vec![Route::graphql(
+        "/journey-planner/v3/graphql",
+        "ServiceJourney",
+        fixture("service_journey_12.json"),
+    )]);
+
+    let journey = server
+        .client()
+        .journey("ATB:ServiceJourney:12_230306097863631_113", "2099-01-01")
+        .await
+        .unwrap();
+
+    let now = chrono::DateTime::parse_from_rfc3339("2099-01-01T12:09:00+01:00").unwrap();
+    assert_eq!(
+        journey.vehicle_position(&now),
+        Some(VehiclePosition::Departed(1))
+    );
+
+    let mut out = Vec::new();
+    render::print_journey(&mut out, &journey, &now).unwrap();
+    let raw = String::from_utf8(out).unwrap();
+    let output = strip_ansi(&raw);
+
+    let expected = " 12  Lade via sentrum
+
+  Aimed  Expected  Actual
+  12:00  12:01     12:01   Dragvoll Spor 1
+  12:06  12:08     12:08   Gløshaugen Spor 2
+▶ ···
+  12:10  12:12             Tyholt Spor 1
+  12:20  12:21             Lade Spor 3
+";
+    assert_eq!(output, expected);
+    assert!(raw.contains("\x1b[2m12:06"));
+    assert!(!raw.contains("\x1b[2m12:10"));
+}
+
+#[test]
+fn vehicles_show_next_stop_delay_and_age() {
+    let vehicles: Vec<busterminal::model::Vehicle> = serde_json::from_value(serde_json::json!([
+        {
+            "vehicleId": "1734",
+            "line": { "lineRef": "ATB:Line:2_12", "publicCode": "12", "lineName": "Dragvoll - Lade" },
+            "serviceJourney": { "id": "ATB:ServiceJourney:12_114" },
+            "destinationName": "Lade via sentrum",
+            "lastUpdated": "2099-01-01T12:09:15+01:00",
+            "delay": 185.0,
+            "monitoredCall": { "stopPointRef": "NSR:Quay:73976", "vehicleAtStop": true },
+            "nextQuay": { "id": "NSR:Quay:73976", "name": "Tyholt", "publicCode": "1", "description": null }
+        },
+        {
+            "line": { "lineRef": "ATB:Line:2_12", "publicCode": "12", "lineName": "Dragvoll - Lade" },
+            "destinationName": "Dragvoll",
+            "lastUpdated": "2099-01-01T11:59:00+01:00",
+            "delay": 10.0,
+            "monitoredCall": { "stopPointRef": "NSR:Quay:71185", "vehicleAtStop": false }
+        }
+    ]))
+    .unwrap();
+
+    let now = chrono::DateTime::parse_from_rfc3339("2099-01-01T12:10:00+01:00").unwrap();
+    let mut out = Vec::new();
+    render::print_vehicles(&mut out, &vehicles, &now).unwrap();
+    let raw = String::from_utf8(out).unwrap();
+
+    let expected = " 12  Lade via sentrum
+     At stop: Tyholt (1)
+     +3 min · updated 45s ago
+     ATB:ServiceJourney:12_114 · vehicle 1734
+
+ 12  Dragvoll
+     Next stop: NSR:Quay:71185
+     on time · updated 11 min ago
+";
+    assert_eq!(strip_ansi(&raw), expected);
+    assert!(raw.contains("\x1b[33mupdated 11 min ago"));
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use busterminal::time::{parse_date, parse_date_time, parse_duration};
+use chrono::{DateTime, FixedOffset};
+
+#[test]
+fn durations_with_units() {
+    assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+    assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+    assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+    assert_eq!(parse_duration(" 45 "), Ok(Duration::from_secs(45)));
+}
+
+#[test]
+fn invalid_durations() {
+    assert!(parse_duration("").is_err());
+    assert!(parse_duration("soon").is_err());
+    assert!(parse_duration("10x").is_err());
+    assert!(parse_duration("5m3").is_err());
+    assert!(parse_duration("0").is_err());
+    assert!(parse_duration("0s").is_err());
+    assert!(parse_duration("9999999999999999d").is_err());
+    assert!(parse_duration("18446744073709551615s1s").is_err());
+}
+
+fn now() -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339("2026-10-18T21:05:00+02:00").unwrap()
+}
+
+#[test]
+fn dates() {
+    let today = now().date_naive();
+
+    assert_eq!(parse_date("today", today), Ok(today));
+    assert_eq!(
+        parse_date("Tomorrow", today).unwrap().to_string(),
+        "2026-10-19"
+    );
+    assert_eq!(
+        parse_date("2026-12-24", today).unwrap().to_string(),
+        "2026-12-24"
+    );
+    assert!(parse_date("someday", today).is_err());
+}
+
+#[test]
+fn date_times() {
+    let parse = |value| parse_date_time(value, &now()).map(|time| time.to_rfc3339());
+
+    assert_eq!(
+        parse("08:15"),
+        Ok(String::from("2026-10-18T08:15:00+02:00"))
+    );
+    assert_eq!(
+        parse("tomorrow 07:30"),
+        Ok(String::from("2026-10-19T07:30:00+02:00"))
+    );
+    assert_eq!(
+        parse("2026-12-24 18:00"),
+        Ok(String::from("2026-12-24T18:00:00+02:00"))
+    );
+    assert_eq!(
+        parse("2026-12-24T18:00"),
+        Ok(String::from("2026-12-24T18:00:00+02:00"))
+    );
+    assert_eq!(
+        parse("2026-12-24T18:00:00+01:00"),
+        Ok(String::from("2026-12-24T18:00:00+01:00"))
+    );
+    assert!(parse("25:00").is_err());
+    assert!(parse("later 08:00").is_err());
+}
@@ -0,0 +1,34 @@
+use busterminal::model::Mode;
+use busterminal::trip::TripOptions;
+
+#[test]
+fn all_modes_when_nothing_is_chosen() {
+    assert_eq!(TripOptions::default().transport_modes(), None);
+}
+
+#[test]
+fn excluded_modes_are_removed_from_all_transit_modes() {
+    let options = TripOptions {
+        exclude_modes: vec![Mode::water, Mode::air],
+        ..Default::default()
+    };
+
+    let modes = options.transport_modes().unwrap();
+
+    assert!(modes.contains(&Mode::rail));
+    assert!(modes.contains(&Mode::bus));
+    assert!(!modes.contains(&Mode::water));
+    assert!(!modes.contains(&Mode::air));
+    assert!(!modes.contains(&Mode::foot));
+}
+
+#[test]
+fn chosen_modes_keep_only_transit() {
+    let options = TripOptions {
+        modes: vec![Mode::rail, Mode::foot, Mode::bus],
+        exclude_modes: vec![Mode::bus],
+        ..Default::default()
+    };
+
+    assert_eq!(options.transport_modes(), Some(vec![Mode::rail]));
+}